@@ -0,0 +1,210 @@
+//! Timing of the parse and part functions, and comparison against a saved
+//! baseline.
+
+use common::{ParseError, Solution};
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::time::{Duration, Instant};
+
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Time parse, part1 and part2 of `S` on `content`, `runs` times each.
+pub fn bench<S: Solution>(content: &str, runs: usize) -> Result<[Stats; 3], ParseError> {
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let input = S::parse(black_box(content))?;
+        samples[0].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&input));
+        samples[1].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&input));
+        samples[2].push(start.elapsed());
+    }
+    let [parse, part1, part2] = samples;
+    Ok([
+        Stats::from_samples(parse),
+        Stats::from_samples(part1),
+        Stats::from_samples(part2),
+    ])
+}
+
+/// Median times by day and stage, saved as one "day stage nanoseconds" line each.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    medians: BTreeMap<(u32, String), Duration>,
+}
+
+impl Baseline {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+        Baseline::parse(&content).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Save the medians to `path`. The medians of other days and stages that
+    /// are already in the file are kept, so that benchmarking one day does not
+    /// drop the baseline of the others.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let mut baseline = match fs::read_to_string(path) {
+            Ok(content) => Baseline::parse(&content).map_err(|e| format!("{}: {}", path, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Baseline::default(),
+            Err(e) => return Err(format!("Cannot open {}: {}", path, e)),
+        };
+        baseline.merge(self);
+        fs::write(path, baseline.to_string()).map_err(|e| format!("Cannot write {}: {}", path, e))
+    }
+
+    /// Add the medians of `other`, replacing those of the same day and stage.
+    pub fn merge(&mut self, other: &Baseline) {
+        for (key, median) in &other.medians {
+            self.medians.insert(key.clone(), *median);
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let mut baseline = Baseline::default();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (day, nanos) = match fields[..] {
+                [day, _, nanos] => (day.parse().ok(), nanos.parse().ok()),
+                _ => (None, None),
+            };
+            match (day, nanos) {
+                (Some(day), Some(nanos)) => {
+                    baseline.insert(day, fields[1], Duration::from_nanos(nanos));
+                }
+                _ => return Err(format!("line {}: expected day, stage and time", i + 1)),
+            }
+        }
+        Ok(baseline)
+    }
+
+    pub fn get(&self, day: u32, stage: &str) -> Option<Duration> {
+        self.medians.get(&(day, stage.to_string())).copied()
+    }
+
+    pub fn insert(&mut self, day: u32, stage: &str, median: Duration) {
+        self.medians.insert((day, stage.to_string()), median);
+    }
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "# day stage median_ns")?;
+        for ((day, stage), median) in &self.medians {
+            writeln!(f, "{} {} {}", day, stage, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+fn ms(d: Duration) -> String {
+    format!("{:.3}", d.as_secs_f64() * 1000.0)
+}
+
+pub fn print_header(with_baseline: bool) {
+    print!(
+        "{:>3} {:<5} {:>10} {:>10} {:>10}",
+        "Day", "Stage", "Min ms", "Median ms", "Max ms"
+    );
+    if with_baseline {
+        print!(" {:>11} {:>8}", "Baseline ms", "Change");
+    }
+    println!();
+}
+
+pub fn print_row(day: u32, stage: &str, stats: &Stats, baseline: Option<&Baseline>) {
+    print!(
+        "{:>3} {:<5} {:>10} {:>10} {:>10}",
+        day,
+        stage,
+        ms(stats.min),
+        ms(stats.median),
+        ms(stats.max)
+    );
+    if let Some(baseline) = baseline {
+        match baseline.get(day, stage) {
+            Some(old) if !old.is_zero() => {
+                let change = stats.median.as_secs_f64() / old.as_secs_f64() - 1.0;
+                print!(" {:>11} {:>+7.1}%", ms(old), change * 100.0);
+            }
+            Some(old) => print!(" {:>11} {:>8}", ms(old), "-"),
+            None => print!(" {:>11} {:>8}", "-", "-"),
+        }
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 4, 2, 3].iter().map(|&x| Duration::from_millis(x));
+        let stats = Stats::from_samples(samples.collect());
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_baseline() {
+        let mut baseline = Baseline::default();
+        baseline.insert(15, "part2", Duration::from_nanos(1_234_567));
+        baseline.insert(2, "parse", Duration::from_nanos(890));
+        let saved = baseline.to_string();
+        assert_eq!(
+            saved,
+            "# day stage median_ns\n2 parse 890\n15 part2 1234567\n"
+        );
+        assert_eq!(Baseline::parse(&saved).unwrap(), baseline);
+        assert!(Baseline::parse("1 parse").is_err());
+    }
+
+    #[test]
+    fn test_save_merges() {
+        let path = std::env::temp_dir().join(format!("aoc_baseline_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+        let mut all = Baseline::default();
+        all.insert(1, "parse", Duration::from_nanos(100));
+        all.insert(2, "part1", Duration::from_nanos(200));
+        all.save(path).unwrap();
+
+        let mut day2 = Baseline::default();
+        day2.insert(2, "part1", Duration::from_nanos(250));
+        day2.save(path).unwrap();
+        let saved = Baseline::load(path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(saved.get(1, "parse"), Some(Duration::from_nanos(100)));
+        assert_eq!(saved.get(2, "part1"), Some(Duration::from_nanos(250)));
+    }
+}
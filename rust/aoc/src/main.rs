@@ -0,0 +1,431 @@
+//! Runner for all 2020: Advent of Code solutions
+//! By Peter Fornwall
+//!
+//! Usage: aoc [--day N] [--part 1|2|all] [--input PATH|-] [--verify|--record] [--json]
+//!        aoc --bench [--day N] [--runs N] [--baseline PATH] [--save-baseline PATH]
+//!        aoc --generate --day N [--seed N] [--size N]
+//!
+//! Without --day, all days are run. Inputs are read from the directory in
+//! AOC_INPUT_DIR, or the inputs directory of the repository. --input reads
+//! the input of the selected day from PATH, or from stdin if PATH is -.
+//!
+//! --verify checks the answers against answers.toml next to the inputs, and
+//! --record stores them there.
+//!
+//! --json prints one JSON object per line for each solved part, with the day,
+//! part, answer, duration in milliseconds, input path (null for stdin) and
+//! the verdict if verifying.
+//!
+//! --bench times parse, part1 and part2 of each day --runs times (default 10)
+//! and prints min, median and max. The medians can be saved with
+//! --save-baseline, which keeps the medians of other days already in the
+//! file, and compared against with --baseline.
+//!
+//! --generate prints a random input for the day, made from --seed (default 0)
+//! with about --size (default 100) entries, and prints its answers to stderr.
+//! Only some days have a generator.
+
+use common::{
+    answers_path, input_hash, Answer, AnswerDb, Generated, InputSource, ParseError, Solution,
+    Verdict,
+};
+use serde_json::json;
+use std::env;
+use std::process;
+use std::time::{Duration, Instant};
+
+mod bench;
+
+const NR_DAYS: u32 = 24;
+
+// Parse the input and solve the selected parts.
+type RunFn = fn(&str, Part) -> Result<Vec<PartResult>, ParseError>;
+
+// Time parse, part1 and part2 the given number of runs.
+type BenchFn = fn(&str, usize) -> Result<[bench::Stats; 3], ParseError>;
+
+// Random input from a seed and a size, with its answers.
+type GenerateFn = fn(u64, usize) -> Generated;
+
+struct Day {
+    run: RunFn,
+    bench: BenchFn,
+    generate: Option<GenerateFn>,
+}
+
+struct PartResult {
+    part: usize,
+    answer: Answer,
+    duration: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    One,
+    Two,
+    All,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Solve,
+    Verify,
+    Record,
+    Bench,
+    Generate,
+}
+
+struct Args {
+    day: Option<u32>,
+    part: Part,
+    input: Option<String>,
+    mode: Mode,
+    json: bool,
+    runs: usize,
+    baseline: Option<String>,
+    save_baseline: Option<String>,
+    seed: u64,
+    size: usize,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut day = None;
+    let mut part = Part::All;
+    let mut input = None;
+    let mut mode = Mode::Solve;
+    let mut json = false;
+    let mut runs = 10;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut seed = 0;
+    let mut size = 100;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                let nr: u32 = value
+                    .parse()
+                    .map_err(|_| format!("Invalid day: {}", value))?;
+                if !(1..=NR_DAYS).contains(&nr) {
+                    return Err(format!("Day must be between 1 and {}", NR_DAYS));
+                }
+                day = Some(nr);
+            }
+            "--part" => {
+                part = match value()?.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    "all" => Part::All,
+                    other => return Err(format!("Invalid part: {}", other)),
+                };
+            }
+            "--input" => input = Some(value()?.clone()),
+            "--verify" | "--record" | "--bench" | "--generate" => {
+                if mode != Mode::Solve {
+                    return Err(String::from(
+                        "Only one of --verify, --record, --bench and --generate can be given",
+                    ));
+                }
+                mode = match arg.as_str() {
+                    "--verify" => Mode::Verify,
+                    "--record" => Mode::Record,
+                    "--bench" => Mode::Bench,
+                    _ => Mode::Generate,
+                };
+            }
+            "--json" => json = true,
+            "--runs" => {
+                let value = value()?;
+                runs = match value.parse() {
+                    Ok(nr) if nr > 0 => nr,
+                    _ => return Err(format!("Invalid number of runs: {}", value)),
+                };
+            }
+            "--baseline" => baseline = Some(value()?.clone()),
+            "--save-baseline" => save_baseline = Some(value()?.clone()),
+            "--seed" => {
+                let value = value()?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("Invalid seed: {}", value))?;
+            }
+            "--size" => {
+                let value = value()?;
+                size = value
+                    .parse()
+                    .map_err(|_| format!("Invalid size: {}", value))?;
+            }
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    if input.is_some() && day.is_none() {
+        return Err(String::from("--input requires --day"));
+    }
+    if (baseline.is_some() || save_baseline.is_some()) && mode != Mode::Bench {
+        return Err(String::from(
+            "--baseline and --save-baseline require --bench",
+        ));
+    }
+    if json && mode == Mode::Bench {
+        return Err(String::from("--json cannot be used with --bench"));
+    }
+    if mode == Mode::Generate && (day.is_none() || input.is_some() || json) {
+        return Err(String::from(
+            "--generate requires --day, and cannot be used with --input or --json",
+        ));
+    }
+    Ok(Args {
+        day,
+        part,
+        input,
+        mode,
+        json,
+        runs,
+        baseline,
+        save_baseline,
+        seed,
+        size,
+    })
+}
+
+fn solve(part: usize, solver: impl Fn() -> Answer) -> PartResult {
+    let start = Instant::now();
+    let answer = solver();
+    PartResult {
+        part,
+        answer,
+        duration: start.elapsed(),
+    }
+}
+
+fn run<S: Solution>(content: &str, part: Part) -> Result<Vec<PartResult>, ParseError> {
+    let input = S::parse(content)?;
+    let mut results = Vec::new();
+    if part != Part::Two {
+        results.push(solve(1, || S::part1(&input)));
+    }
+    if part != Part::One {
+        results.push(solve(2, || S::part2(&input)));
+    }
+    Ok(results)
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        run: run::<S>,
+        bench: bench::bench::<S>,
+        generate: None,
+    }
+}
+
+impl Day {
+    const fn with_generator(self, generate: GenerateFn) -> Day {
+        Day {
+            generate: Some(generate),
+            ..self
+        }
+    }
+}
+
+const DAYS: [Day; NR_DAYS as usize] = [
+    day::<day1::Day1>().with_generator(day1::generate::generate),
+    day::<day2::Day2>().with_generator(day2::generate::generate),
+    day::<day3::Day3>(),
+    day::<day4::Day4>().with_generator(day4::generate::generate),
+    day::<day5::Day5>().with_generator(day5::generate::generate),
+    day::<day6::Day6>(),
+    day::<day7::Day7>().with_generator(day7::generate::generate),
+    day::<day8::Day8>().with_generator(day8::generate::generate),
+    day::<day9::Day9>(),
+    day::<day10::Day10>(),
+    day::<day11::Day11>(),
+    day::<day12::Day12>(),
+    day::<day13::Day13>(),
+    day::<day14::Day14>(),
+    day::<day15::Day15>(),
+    day::<day16::Day16>(),
+    day::<day17::Day17>(),
+    day::<day18::Day18>(),
+    day::<day19::Day19>(),
+    day::<day20::Day20>().with_generator(day20::generate::generate),
+    day::<day21::Day21>(),
+    day::<day22::Day22>(),
+    day::<day23::Day23>(),
+    day::<day24::Day24>(),
+];
+
+// Returns false if an answer failed verification.
+fn run_day(day: u32, args: &Args, source: &InputSource, db: &mut AnswerDb) -> Result<bool, String> {
+    let content = source
+        .read()
+        .map_err(|e| format!("Cannot open {}: {}", source, e))?;
+    let results = (DAYS[day as usize - 1].run)(&content, args.part)
+        .map_err(|e| format!("{}: {}", source, e))?;
+    let hash = input_hash(&content);
+    let mut passed = true;
+    for result in results {
+        let verdict = match args.mode {
+            Mode::Verify => Some(db.verify(day, &hash, result.part, &result.answer)),
+            _ => None,
+        };
+        if args.json {
+            println!("{}", to_json(day, &result, source, verdict.as_ref()));
+        } else if let Some(verdict) = &verdict {
+            println!(
+                "Day {} part {}: {} {}",
+                day, result.part, result.answer, verdict
+            );
+        } else {
+            println!("Day {} part {}: {}", day, result.part, result.answer);
+        }
+        passed &= !matches!(verdict, Some(Verdict::Fail(_)));
+        if args.mode == Mode::Record {
+            db.insert(day, &hash, result.part, result.answer);
+        }
+    }
+    Ok(passed)
+}
+
+fn answer_to_json(answer: &Answer) -> serde_json::Value {
+    match answer {
+        Answer::Number(nr) => json!(nr),
+        Answer::Text(text) => json!(text),
+    }
+}
+
+fn to_json(
+    day: u32,
+    result: &PartResult,
+    source: &InputSource,
+    verdict: Option<&Verdict>,
+) -> serde_json::Value {
+    let mut value = json!({
+        "day": day,
+        "part": result.part,
+        "answer": answer_to_json(&result.answer),
+        "duration_ms": result.duration.as_secs_f64() * 1000.0,
+        "input": match source {
+            InputSource::File(path) => json!(path.display().to_string()),
+            InputSource::Stdin => json!(null),
+        },
+    });
+    match verdict {
+        Some(Verdict::Pass) => value["verdict"] = json!("PASS"),
+        Some(Verdict::Fail(expected)) => {
+            value["verdict"] = json!("FAIL");
+            value["expected"] = answer_to_json(expected);
+        }
+        Some(Verdict::Unknown) => value["verdict"] = json!("UNKNOWN"),
+        None => {}
+    }
+    value
+}
+
+fn bench_day(
+    day: u32,
+    args: &Args,
+    source: &InputSource,
+    baseline: Option<&bench::Baseline>,
+    new_baseline: &mut bench::Baseline,
+) -> Result<(), String> {
+    let content = source
+        .read()
+        .map_err(|e| format!("Cannot open {}: {}", source, e))?;
+    let stats = (DAYS[day as usize - 1].bench)(&content, args.runs)
+        .map_err(|e| format!("{}: {}", source, e))?;
+    for (stage, stats) in bench::STAGES.iter().zip(&stats) {
+        bench::print_row(day, stage, stats, baseline);
+        new_baseline.insert(day, stage, stats.median);
+    }
+    Ok(())
+}
+
+fn generate_day(day: u32, args: &Args) -> Result<(), String> {
+    let generate = DAYS[day as usize - 1]
+        .generate
+        .ok_or(format!("Day {} has no generator", day))?;
+    let generated = generate(args.seed, args.size);
+    println!("{}", generated.input);
+    eprintln!("Day {} part 1: {}", day, generated.part1);
+    eprintln!("Day {} part 2: {}", day, generated.part2);
+    Ok(())
+}
+
+fn exit_on_error<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = parse_args(&args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        eprintln!(
+            "Usage: aoc [--day N] [--part 1|2|all] [--input PATH|-] [--verify|--record] [--json]"
+        );
+        eprintln!(
+            "       aoc --bench [--day N] [--runs N] [--baseline PATH] [--save-baseline PATH]"
+        );
+        eprintln!("       aoc --generate --day N [--seed N] [--size N]");
+        process::exit(2);
+    });
+
+    if args.mode == Mode::Generate {
+        exit_on_error(generate_day(args.day.unwrap(), &args));
+        return;
+    }
+
+    let db_path = answers_path();
+    let mut db = match args.mode {
+        Mode::Verify | Mode::Record => exit_on_error(AnswerDb::load(&db_path)),
+        _ => AnswerDb::new(),
+    };
+
+    let days: Vec<u32> = match args.day {
+        Some(day) => vec![day],
+        None => (1..=NR_DAYS).collect(),
+    };
+
+    if args.mode == Mode::Bench {
+        let baseline = args
+            .baseline
+            .as_ref()
+            .map(|path| exit_on_error(bench::Baseline::load(path)));
+        let mut new_baseline = bench::Baseline::default();
+        bench::print_header(baseline.is_some());
+        for day in days {
+            let source = InputSource::from_arg(args.input.as_deref(), day);
+            exit_on_error(bench_day(
+                day,
+                &args,
+                &source,
+                baseline.as_ref(),
+                &mut new_baseline,
+            ));
+        }
+        if let Some(path) = &args.save_baseline {
+            exit_on_error(new_baseline.save(path));
+        }
+        return;
+    }
+
+    let mut all_passed = true;
+    for day in days {
+        let source = InputSource::from_arg(args.input.as_deref(), day);
+        all_passed &= exit_on_error(run_day(day, &args, &source, &mut db));
+    }
+
+    if args.mode == Mode::Record {
+        if let Err(e) = db.save(&db_path) {
+            eprintln!("Cannot write {}: {}", db_path.display(), e);
+            process::exit(1);
+        }
+    }
+    if !all_passed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,167 @@
+//! Runs every day on the example inputs in tests/examples/dayN/*.txt, and
+//! checks the answers listed in the .toml file next to each input. Only the
+//! listed parts are run, since some parts need more than an example input.
+
+use common::{Answer, Solution};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn expected_answer(path: &Path, key: &str, value: &toml::Value) -> Answer {
+    match value {
+        toml::Value::Integer(nr) => Answer::Number(*nr),
+        toml::Value::String(text) => Answer::Text(text.clone()),
+        _ => panic!("{}: {} must be a number or a string", path.display(), key),
+    }
+}
+
+fn check<S: Solution>(day: u32) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/examples")
+        .join(format!("day{}", day));
+    let mut inputs: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Cannot open {}: {}", dir.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("txt".as_ref()))
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "No examples in {}", dir.display());
+
+    for path in inputs {
+        let content = fs::read_to_string(&path).unwrap();
+        let answers_path = path.with_extension("toml");
+        let answers: toml::Table = fs::read_to_string(&answers_path)
+            .unwrap_or_else(|e| panic!("Cannot open {}: {}", answers_path.display(), e))
+            .parse()
+            .unwrap_or_else(|e| panic!("{}: {}", answers_path.display(), e));
+        let input = S::parse(&content).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        for (key, value) in &answers {
+            let answer = match key.as_str() {
+                "part1" => S::part1(&input),
+                "part2" => S::part2(&input),
+                _ => panic!("{}: unknown part '{}'", answers_path.display(), key),
+            };
+            let expected = expected_answer(&answers_path, key, value);
+            assert_eq!(answer, expected, "{} {}", path.display(), key);
+        }
+    }
+}
+
+#[test]
+fn day1() {
+    check::<day1::Day1>(1);
+}
+
+#[test]
+fn day2() {
+    check::<day2::Day2>(2);
+}
+
+#[test]
+fn day3() {
+    check::<day3::Day3>(3);
+}
+
+#[test]
+fn day4() {
+    check::<day4::Day4>(4);
+}
+
+#[test]
+fn day5() {
+    check::<day5::Day5>(5);
+}
+
+#[test]
+fn day6() {
+    check::<day6::Day6>(6);
+}
+
+#[test]
+fn day7() {
+    check::<day7::Day7>(7);
+}
+
+#[test]
+fn day8() {
+    check::<day8::Day8>(8);
+}
+
+#[test]
+fn day9() {
+    check::<day9::Day9>(9);
+}
+
+#[test]
+fn day10() {
+    check::<day10::Day10>(10);
+}
+
+#[test]
+fn day11() {
+    check::<day11::Day11>(11);
+}
+
+#[test]
+fn day12() {
+    check::<day12::Day12>(12);
+}
+
+#[test]
+fn day13() {
+    check::<day13::Day13>(13);
+}
+
+#[test]
+fn day14() {
+    check::<day14::Day14>(14);
+}
+
+#[test]
+fn day15() {
+    check::<day15::Day15>(15);
+}
+
+#[test]
+fn day16() {
+    check::<day16::Day16>(16);
+}
+
+#[test]
+fn day17() {
+    check::<day17::Day17>(17);
+}
+
+#[test]
+fn day18() {
+    check::<day18::Day18>(18);
+}
+
+#[test]
+fn day19() {
+    check::<day19::Day19>(19);
+}
+
+#[test]
+fn day20() {
+    check::<day20::Day20>(20);
+}
+
+#[test]
+fn day21() {
+    check::<day21::Day21>(21);
+}
+
+#[test]
+fn day22() {
+    check::<day22::Day22>(22);
+}
+
+#[test]
+fn day23() {
+    check::<day23::Day23>(23);
+}
+
+#[test]
+fn day24() {
+    check::<day24::Day24>(24);
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle. Most answers are numbers, but a few
+/// (e.g. day 21 part 2 and day 23) are strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(nr) => write!(f, "{}", nr),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(nr: i32) -> Self {
+        Answer::Number(nr as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(nr: i64) -> Self {
+        Answer::Number(nr)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(nr: usize) -> Self {
+        Answer::Number(nr as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
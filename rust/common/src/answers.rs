@@ -0,0 +1,192 @@
+use crate::{input_dir, Answer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Known answers, keyed by day, input hash and part. Stored as TOML with one
+/// table per day and input:
+///
+/// ```toml
+/// [day10.f3a1c2d4e5b60718]
+/// part1 = 1917
+/// part2 = 113387824750592
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct AnswerDb {
+    answers: BTreeMap<(u32, String), BTreeMap<usize, Answer>>,
+}
+
+/// Result of checking an answer against the database.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(Answer),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(expected) => write!(f, "FAIL (expected {})", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// Hash identifying an input, the 64-bit FNV-1a hash of the trimmed content
+/// as 16 hex digits. Stable between builds, unlike the std hashers.
+pub fn input_hash(content: &str) -> String {
+    let hash = content
+        .trim()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+            (hash ^ b as u64).wrapping_mul(0x100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Default location of the answers file, next to the inputs.
+pub fn answers_path() -> PathBuf {
+    input_dir().join("answers.toml")
+}
+
+fn parse_day(key: &str) -> Option<u32> {
+    key.strip_prefix("day")?.parse().ok()
+}
+
+fn parse_part(key: &str) -> Option<usize> {
+    match key {
+        "part1" => Some(1),
+        "part2" => Some(2),
+        _ => None,
+    }
+}
+
+impl AnswerDb {
+    pub fn new() -> Self {
+        AnswerDb::default()
+    }
+
+    /// Read the database from `path`. A missing file is an empty database.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                AnswerDb::from_toml(&content).map_err(|e| format!("{}: {}", path.display(), e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AnswerDb::new()),
+            Err(e) => Err(format!("Cannot open {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content.parse().map_err(|e| format!("{}", e))?;
+        let mut db = AnswerDb::new();
+        for (day_key, inputs) in &table {
+            let day = parse_day(day_key).ok_or(format!("invalid day '{}'", day_key))?;
+            let inputs = inputs
+                .as_table()
+                .ok_or(format!("{} must be a table", day_key))?;
+            for (hash, parts) in inputs {
+                let parts = parts
+                    .as_table()
+                    .ok_or(format!("{}.{} must be a table", day_key, hash))?;
+                for (part_key, value) in parts {
+                    let part = parse_part(part_key).ok_or(format!(
+                        "invalid part '{}' in {}.{}",
+                        part_key, day_key, hash
+                    ))?;
+                    let answer = match value {
+                        toml::Value::Integer(nr) => Answer::Number(*nr),
+                        toml::Value::String(text) => Answer::Text(text.clone()),
+                        _ => {
+                            return Err(format!(
+                                "{}.{}.{} must be a number or a string",
+                                day_key, hash, part_key
+                            ))
+                        }
+                    };
+                    db.insert(day, hash, part, answer);
+                }
+            }
+        }
+        Ok(db)
+    }
+
+    /// Days are written in numerical order, which a TOML serializer would not do.
+    pub fn to_toml(&self) -> String {
+        let mut tables = Vec::new();
+        for ((day, hash), parts) in &self.answers {
+            let mut table = format!("[day{}.{}]\n", day, hash);
+            for (part, answer) in parts {
+                let value = match answer {
+                    Answer::Number(nr) => toml::Value::Integer(*nr),
+                    Answer::Text(text) => toml::Value::String(text.clone()),
+                };
+                table += &format!("part{} = {}\n", part, value);
+            }
+            tables.push(table);
+        }
+        tables.join("\n")
+    }
+
+    pub fn get(&self, day: u32, hash: &str, part: usize) -> Option<&Answer> {
+        self.answers.get(&(day, hash.to_string()))?.get(&part)
+    }
+
+    pub fn insert(&mut self, day: u32, hash: &str, part: usize, answer: Answer) {
+        self.answers
+            .entry((day, hash.to_string()))
+            .or_default()
+            .insert(part, answer);
+    }
+
+    pub fn verify(&self, day: u32, hash: &str, part: usize, answer: &Answer) -> Verdict {
+        match self.get(day, hash, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("1\n2\n"), input_hash("1\n2"));
+        assert_ne!(input_hash("1\n2"), input_hash("2\n1"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut db = AnswerDb::new();
+        db.insert(10, "abc", 1, Answer::Number(1917));
+        db.insert(2, "def", 2, Answer::Text(String::from("kbmlt,mrccxm")));
+        let toml = db.to_toml();
+        assert!(toml.starts_with("[day2.def]\npart2 = \"kbmlt,mrccxm\"\n"));
+        assert_eq!(AnswerDb::from_toml(&toml).unwrap(), db);
+    }
+
+    #[test]
+    fn test_verify() {
+        let db = AnswerDb::from_toml("[day1.abc]\npart1 = 5\n").unwrap();
+        assert_eq!(db.verify(1, "abc", 1, &Answer::Number(5)), Verdict::Pass);
+        assert_eq!(
+            db.verify(1, "abc", 1, &Answer::Number(6)),
+            Verdict::Fail(Answer::Number(5))
+        );
+        assert_eq!(db.verify(1, "abc", 2, &Answer::Number(5)), Verdict::Unknown);
+        assert_eq!(db.verify(1, "xyz", 1, &Answer::Number(5)), Verdict::Unknown);
+        assert!(AnswerDb::from_toml("[day1.abc]\npart3 = 5\n").is_err());
+    }
+}
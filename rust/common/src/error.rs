@@ -0,0 +1,91 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when a puzzle input cannot be parsed.
+///
+/// Line and column are 1-based, and the column is counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Error located at the start of `token`, which should be a slice of
+    /// `content`. If it is not, the error is located at the end of `content`.
+    pub fn at(content: &str, token: &str, message: impl Into<String>) -> Self {
+        let (line, column) = position(content, offset_in(content, token));
+        ParseError::new(line, column, message)
+    }
+
+    /// Error located just after `token`, which should be a slice of `content`.
+    /// Used when something is missing after the token.
+    pub fn after(content: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (offset_in(content, token) + token.len()).min(content.len());
+        let (line, column) = position(content, offset);
+        ParseError::new(line, column, message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+fn offset_in(content: &str, token: &str) -> usize {
+    let start = content.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+    if pos >= start && pos <= start + content.len() {
+        pos - start
+    } else {
+        content.len()
+    }
+}
+
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let content = "12\n34 x5\n";
+        let token = &content[6..7];
+        let e = ParseError::at(content, token, "bad");
+        assert_eq!((e.line, e.column), (2, 4));
+        assert_eq!(e.to_string(), "line 2, column 4: bad");
+
+        let e = ParseError::after(content, &content[3..5], "missing");
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn test_foreign_token() {
+        let content = "1\n2";
+        let e = ParseError::at(content, "other", "bad");
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+}
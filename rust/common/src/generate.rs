@@ -0,0 +1,80 @@
+use crate::Answer;
+use std::ops::RangeInclusive;
+
+/// A generated puzzle input and its answers, known from how it was built.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+/// Small random number generator (SplitMix64), so that generated inputs only
+/// depend on the seed and are the same on every platform and build.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in 0..n. The bias is negligible for the small n used here.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let size = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % size) as i64
+    }
+
+    /// True with probability `percent` / 100.
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let values: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(values, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(values[0], Rng::new(43).next_u64());
+
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&a.range(-3..=3)));
+            assert!(a.below(5) < 5);
+        }
+        let mut items: Vec<usize> = (0..20).collect();
+        a.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
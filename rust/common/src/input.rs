@@ -0,0 +1,111 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Environment variable that overrides the directory inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Source given as a command line argument: `-` is stdin and anything
+    /// else a path. Without an argument, the default input of `day` is used.
+    pub fn from_arg(arg: Option<&str>, day: u32) -> Self {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::File(default_input_path(day)),
+        }
+    }
+
+    /// Source given as the first argument of the program, see `from_arg`.
+    pub fn from_args(day: u32) -> Self {
+        let arg = env::args().nth(1);
+        InputSource::from_arg(arg.as_deref(), day)
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                Ok(content)
+            }
+            InputSource::File(path) => fs::read_to_string(path),
+        }
+    }
+
+    /// Open the source for reading line by line, instead of all at once.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(BufReader::new(io::stdin()))),
+            InputSource::File(path) => Ok(Box::new(BufReader::new(fs::File::open(path)?))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Directory with the inputs: `AOC_INPUT_DIR` if set, otherwise the
+/// `inputs` directory of the repository.
+pub fn input_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../../inputs"),
+    }
+}
+
+pub fn default_input_path(day: u32) -> PathBuf {
+    input_dir().join(format!("day{}_input.txt", day))
+}
+
+/// Return the content of `source`, or print why it could not be read and exit.
+pub fn read_or_exit(source: &InputSource) -> String {
+    source.read().unwrap_or_else(|e| {
+        eprintln!("Cannot open {}: {}", source, e);
+        process::exit(1);
+    })
+}
+
+/// Return `source` opened for reading, or print why it could not be opened
+/// and exit.
+pub fn open_or_exit(source: &InputSource) -> Box<dyn BufRead> {
+    source.open().unwrap_or_else(|e| {
+        eprintln!("Cannot open {}: {}", source, e);
+        process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg(Some("-"), 3), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("my_input.txt"), 3),
+            InputSource::File(PathBuf::from("my_input.txt"))
+        );
+        assert_eq!(
+            InputSource::from_arg(None, 3),
+            InputSource::File(input_dir().join("day3_input.txt"))
+        );
+        assert_eq!(InputSource::Stdin.to_string(), "<stdin>");
+    }
+}
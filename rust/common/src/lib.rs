@@ -0,0 +1,31 @@
+//! Common code shared by the 2020: Advent of Code solutions
+//! By Peter Fornwall
+
+mod answer;
+mod answers;
+mod error;
+mod generate;
+mod input;
+mod parse;
+mod solution;
+
+pub use answer::Answer;
+pub use answers::{answers_path, input_hash, AnswerDb, Verdict};
+pub use error::ParseError;
+pub use generate::{Generated, Rng};
+pub use input::{
+    default_input_path, input_dir, open_or_exit, read_or_exit, InputSource, INPUT_DIR_VAR,
+};
+pub use parse::{parse_grid, parse_nr};
+pub use solution::Solution;
+
+use std::fmt::Display;
+use std::process;
+
+/// Return the parsed input, or print where parsing of `source` failed and exit.
+pub fn unwrap_or_exit<T>(result: Result<T, ParseError>, source: impl Display) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}: {}", source, e);
+        process::exit(1);
+    })
+}
@@ -0,0 +1,76 @@
+use crate::ParseError;
+use std::str::FromStr;
+
+/// Parse `token`, which should be a slice of `content`, as a number.
+pub fn parse_nr<T: FromStr>(content: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(content, token, format!("invalid number '{}'", token)))
+}
+
+/// Parse a rectangular grid of characters, one row per line. `cell` maps a
+/// character to its value, or returns None if the character is not allowed.
+pub fn parse_grid<T>(
+    content: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut grid: Vec<Vec<T>> = Vec::new();
+    for line in content.trim().split('\n').map(|x| x.trim()) {
+        let mut row = Vec::new();
+        for (i, ch) in line.char_indices() {
+            let value = cell(ch).ok_or_else(|| {
+                ParseError::at(
+                    content,
+                    &line[i..],
+                    format!("unexpected character '{}'", ch),
+                )
+            })?;
+            row.push(value);
+        }
+        if row.is_empty() {
+            return Err(ParseError::at(content, line, "empty row"));
+        }
+        if let Some(first) = grid.first() {
+            if row.len() != first.len() {
+                return Err(ParseError::at(
+                    content,
+                    line,
+                    format!("expected {} columns, found {}", first.len(), row.len()),
+                ));
+            }
+        }
+        grid.push(row);
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nr() {
+        let content = "12 x";
+        let e = parse_nr::<i32>(content, &content[3..]).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 4));
+        assert_eq!(e.message, "invalid number 'x'");
+        assert_eq!(parse_nr::<i32>(content, &content[..2]), Ok(12));
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let cell = |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = parse_grid("#.\n.#\n", cell).unwrap();
+        assert_eq!(grid, vec![vec![true, false], vec![false, true]]);
+
+        let e = parse_grid("#.\n.#.", cell).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        let e = parse_grid("#.\n.x", cell).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.message, "unexpected character 'x'");
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{Answer, ParseError};
+
+/// Interface implemented by every day, so that runners and other tools can
+/// treat all days the same way.
+///
+/// The parsed input may borrow from the input text, hence the lifetime.
+pub trait Solution {
+    type Input<'a>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Answer;
+    fn part2(input: &Self::Input<'_>) -> Answer;
+}
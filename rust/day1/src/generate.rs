@@ -0,0 +1,98 @@
+//! Random expense reports with one pair and one triple of entries that sum to
+//! 2020.
+
+use common::{Generated, Rng};
+use std::collections::HashSet;
+
+// Pick `n` distinct entries below 2020 that sum to 2020.
+fn pick_sum(rng: &mut Rng, n: usize, used: &mut HashSet<i64>) -> Vec<i64> {
+    loop {
+        let mut entries: Vec<i64> = (1..n).map(|_| rng.range(1..=2019)).collect();
+        let last = 2020 - entries.iter().sum::<i64>();
+        entries.push(last);
+        let distinct: HashSet<i64> = entries.iter().copied().collect();
+        if last > 0 && distinct.len() == n && distinct.is_disjoint(used) {
+            used.extend(&entries);
+            return entries;
+        }
+    }
+}
+
+// True if some pair or triple of `entries`, other than `pair` and `triple`,
+// sums to 2020.
+fn has_other_sum(entries: &[i64], pair: &[i64], triple: &[i64]) -> bool {
+    let n = entries.len();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let mut sum_pair = vec![entries[i], entries[j]];
+            sum_pair.sort_unstable();
+            if entries[i] + entries[j] == 2020 && sum_pair != pair {
+                return true;
+            }
+            for k in (j + 1)..n {
+                let mut sum_triple = vec![entries[i], entries[j], entries[k]];
+                sum_triple.sort_unstable();
+                if entries[i] + entries[j] + entries[k] == 2020 && sum_triple != triple {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Report with `size` (at least 5) distinct entries. The entries not in the
+/// pair or triple are above 2020, so they can not be part of any sum.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let (mut pair, mut triple) = loop {
+        let mut used = HashSet::new();
+        let mut pair = pick_sum(&mut rng, 2, &mut used);
+        let mut triple = pick_sum(&mut rng, 3, &mut used);
+        pair.sort_unstable();
+        triple.sort_unstable();
+        let entries: Vec<i64> = pair.iter().chain(&triple).copied().collect();
+        if !has_other_sum(&entries, &pair, &triple) {
+            break (pair, triple);
+        }
+    };
+    let part1 = pair.iter().product::<i64>();
+    let part2 = triple.iter().product::<i64>();
+
+    let mut entries: Vec<i64> = Vec::new();
+    entries.append(&mut pair);
+    entries.append(&mut triple);
+    let mut used: HashSet<i64> = entries.iter().copied().collect();
+    while entries.len() < size.max(5) {
+        let entry = rng.range(2021..=2020 + 4 * size as i64 + 8000);
+        if used.insert(entry) {
+            entries.push(entry);
+        }
+    }
+    rng.shuffle(&mut entries);
+
+    let lines: Vec<String> = entries.iter().map(|x| x.to_string()).collect();
+    Generated {
+        input: lines.join("\n"),
+        part1: part1.into(),
+        part2: part2.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day1;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let generated = generate(seed, 200);
+            let input = Day1::parse(&generated.input).unwrap();
+            assert_eq!(input.len(), 200);
+            assert_eq!(Day1::part1(&input), generated.part1);
+            assert_eq!(Day1::part2(&input), generated.part2);
+        }
+    }
+}
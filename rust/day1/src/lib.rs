@@ -0,0 +1,541 @@
+//! Solutions to 2020: Advent of Code day 1
+//! By Peter Fornwall
+//!
+//! For day 1 I made two different solutions.
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+pub mod generate;
+
+/// Integer type of the expense entries, like i32, i64 or i128. Sums are
+/// worked out in i128, and products are checked to fit in the entry type.
+pub trait Entry: Copy + Ord + fmt::Display + FromStr + Into<i128> + TryFrom<i128> {}
+
+impl<T> Entry for T where T: Copy + Ord + fmt::Display + FromStr + Into<i128> + TryFrom<i128> {}
+
+/// Error when the product of some entries does not fit in the entry type.
+#[derive(Debug, PartialEq)]
+pub struct OverflowError {
+    pub entries: Vec<i128>,
+    pub type_name: &'static str,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries: Vec<String> = self.entries.iter().map(|x| x.to_string()).collect();
+        write!(
+            f,
+            "product of {} does not fit in {}",
+            entries.join(" * "),
+            self.type_name
+        )
+    }
+}
+
+/// Error when no entries sum to the target, or their product overflows.
+#[derive(Debug, PartialEq)]
+pub enum SumError {
+    /// No `k` entries sum to `target`.
+    NotFound {
+        k: usize,
+        target: i128,
+    },
+    Overflow(OverflowError),
+}
+
+impl fmt::Display for SumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SumError::NotFound { k, target } => {
+                let what = match k {
+                    2 => String::from("pair"),
+                    3 => String::from("triple"),
+                    _ => format!("set of {} entries", k),
+                };
+                write!(f, "no {} sums to {}", what, target)
+            }
+            SumError::Overflow(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<OverflowError> for SumError {
+    fn from(e: OverflowError) -> Self {
+        SumError::Overflow(e)
+    }
+}
+
+fn not_found<T: Entry>(k: usize, target: T) -> SumError {
+    SumError::NotFound {
+        k,
+        target: target.into(),
+    }
+}
+
+/// Product of `entries`, or an error if it does not fit in the entry type.
+pub fn product<T: Entry>(entries: &[T]) -> Result<T, OverflowError> {
+    let overflow = || OverflowError {
+        entries: entries.iter().map(|&x| x.into()).collect(),
+        type_name: std::any::type_name::<T>(),
+    };
+    let mut result: i128 = 1;
+    for &x in entries {
+        result = result.checked_mul(x.into()).ok_or_else(overflow)?;
+    }
+    T::try_from(result).map_err(|_| overflow())
+}
+
+// True if the entries sum to `target`. The sum is in i128, so it only
+// overflows for i128 entries, and then it is not `target`.
+fn sums_to<T: Entry>(entries: &[T], target: T) -> bool {
+    let sum = entries
+        .iter()
+        .try_fold(0i128, |sum, &x| sum.checked_add(x.into()));
+    sum == Some(target.into())
+}
+
+// Compare a + b with `target`, also when a + b does not fit in an i128.
+fn cmp_sum(a: i128, b: i128, target: i128) -> Ordering {
+    match a.checked_add(b) {
+        Some(sum) => sum.cmp(&target),
+        None if a > 0 => Ordering::Greater,
+        None => Ordering::Less,
+    }
+}
+
+/// Expense report entries with their multiplicity, so that two equal entries
+/// are still two different entries.
+#[derive(Debug, PartialEq)]
+pub struct Expenses<T = i32> {
+    counts: BTreeMap<T, usize>,
+}
+
+impl<T: Entry> Expenses<T> {
+    /// Number of entries with the given value.
+    pub fn count(&self, value: T) -> usize {
+        self.counts.get(&value).copied().unwrap_or(0)
+    }
+
+    /// Total number of entries.
+    pub fn len(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// True if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Every combination of `k` entries that sums to `target`, each as sorted
+    /// entries. An entry is never used more times than it is in the report,
+    /// and combinations of equal values are only listed once.
+    pub fn find_all(&self, k: usize, target: T) -> Vec<Vec<T>> {
+        let values: Vec<T> = self.counts.keys().copied().collect();
+        let mut left: Vec<usize> = self.counts.values().copied().collect();
+        let mut chosen = Vec::new();
+        let mut results = Vec::new();
+        find_all_from(
+            &values,
+            &mut left,
+            0,
+            k,
+            target.into(),
+            &mut chosen,
+            &mut results,
+        );
+        results
+    }
+}
+
+impl<T: Entry> Default for Expenses<T> {
+    fn default() -> Self {
+        Expenses {
+            counts: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Entry> FromIterator<T> for Expenses<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counts = BTreeMap::new();
+        for value in iter {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        Expenses { counts }
+    }
+}
+
+// Push every combination of k values from values[start..], where value i can
+// be used left[i] more times, that sums to target, after the chosen values.
+fn find_all_from<T: Entry>(
+    values: &[T],
+    left: &mut [usize],
+    start: usize,
+    k: usize,
+    target: i128,
+    chosen: &mut Vec<T>,
+    results: &mut Vec<Vec<T>>,
+) {
+    if k == 0 {
+        if target == 0 {
+            results.push(chosen.clone());
+        }
+        return;
+    }
+    if k == 2 {
+        if start >= values.len() {
+            return;
+        }
+        let (mut low, mut high) = (start, values.len() - 1);
+        while low <= high {
+            let order = cmp_sum(values[low].into(), values[high].into(), target);
+            // The chosen values may already use up an entry.
+            let available = if low < high {
+                left[low] >= 1 && left[high] >= 1
+            } else {
+                left[low] >= 2
+            };
+            if order == Ordering::Equal && available {
+                let mut result = chosen.clone();
+                result.push(values[low]);
+                result.push(values[high]);
+                results.push(result);
+            }
+            if order == Ordering::Less {
+                low += 1;
+            } else if high == 0 {
+                break;
+            } else {
+                high -= 1;
+            }
+        }
+        return;
+    }
+    for i in start..values.len() {
+        if left[i] == 0 {
+            continue;
+        }
+        // A remaining target outside i128 can only be reached by i128 entries
+        // whose sum overflows, so there is nothing to find.
+        let rest = match target.checked_sub(values[i].into()) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        left[i] -= 1;
+        chosen.push(values[i]);
+        find_all_from(values, left, i, k - 1, rest, chosen, results);
+        chosen.pop();
+        left[i] += 1;
+    }
+}
+
+/// Find two entries in values that has the sum search_sum, and return their product.
+pub fn find_product<T: Entry>(
+    values: &Expenses<T>,
+    search_sum: T,
+) -> Result<Option<T>, OverflowError> {
+    values
+        .counts
+        .keys()
+        .find_map(|&x| {
+            let second_value = search_sum.into().checked_sub(x.into())?;
+            let second_value = T::try_from(second_value).ok()?;
+            // The same entry can not be used twice.
+            let needed = if second_value == x { 2 } else { 1 };
+            if values.count(second_value) >= needed {
+                Some(product(&[x, second_value]))
+            } else {
+                None
+            }
+        })
+        .transpose()
+}
+
+/// Product of the two entries that sum to `search_sum`.
+pub fn part1<T: Entry>(values: &Expenses<T>, search_sum: T) -> Result<T, SumError> {
+    find_product(values, search_sum)?.ok_or_else(|| not_found(2, search_sum))
+}
+
+/// Product of the three entries that sum to `search_sum`.
+pub fn part2<T: Entry>(values: &Expenses<T>, search_sum: T) -> Result<T, SumError> {
+    match values.find_all(3, search_sum).first() {
+        Some(entries) => Ok(product(entries)?),
+        None => Err(not_found(3, search_sum)),
+    }
+}
+
+/// Simpler (and in my opinion more readable) solution to part 1, but slower.
+pub fn part1_simple<T: Entry>(v: &[T], search_sum: T) -> Result<T, SumError> {
+    for i in 0..v.len() {
+        for j in (i + 1)..v.len() {
+            let x = v[i];
+            let y = v[j];
+            if sums_to(&[x, y], search_sum) {
+                return Ok(product(&[x, y])?);
+            }
+        }
+    }
+    Err(not_found(2, search_sum))
+}
+
+/// Simpler (and in my opinion more readable) solution to part 2, but slower.
+pub fn part2_simple<T: Entry>(v: &[T], search_sum: T) -> Result<T, SumError> {
+    for i in 0..v.len() {
+        for j in (i + 1)..v.len() {
+            for k in (j + 1)..(v.len()) {
+                let x = v[i];
+                let y = v[j];
+                let z = v[k];
+                if sums_to(&[x, y, z], search_sum) {
+                    return Ok(product(&[x, y, z])?);
+                }
+            }
+        }
+    }
+    Err(not_found(3, search_sum))
+}
+
+/// Entries found by `find_k_sum`, ordered by their indices in the values.
+#[derive(Debug, PartialEq)]
+pub struct KSum<T = i32> {
+    pub entries: Vec<T>,
+    pub indices: Vec<usize>,
+}
+
+// Find k of the sorted (value, index) pairs in `sorted` that sum to `target`,
+// pushing their positions in `sorted` to `chosen`.
+fn find_sorted(sorted: &[(i128, usize)], k: usize, target: i128, chosen: &mut Vec<usize>) -> bool {
+    let n = sorted.len();
+    if k == 0 {
+        return target == 0;
+    }
+    if k > n {
+        return false;
+    }
+    // The k smallest and k largest values bound every possible sum.
+    let checked_sum =
+        |slice: &[(i128, usize)]| slice.iter().try_fold(0i128, |sum, x| sum.checked_add(x.0));
+    if let (Some(min_sum), Some(max_sum)) =
+        (checked_sum(&sorted[..k]), checked_sum(&sorted[(n - k)..]))
+    {
+        if target < min_sum || target > max_sum {
+            return false;
+        }
+    }
+    if k == 1 {
+        return match sorted.binary_search_by_key(&target, |x| x.0) {
+            Ok(i) => {
+                chosen.push(i);
+                true
+            }
+            Err(_) => false,
+        };
+    }
+    if k == 2 {
+        let (mut low, mut high) = (0, n - 1);
+        while low < high {
+            match cmp_sum(sorted[low].0, sorted[high].0, target) {
+                Ordering::Equal => {
+                    chosen.push(low);
+                    chosen.push(high);
+                    return true;
+                }
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+            }
+        }
+        return false;
+    }
+    for first in 0..=(n - k) {
+        // Starting with an equal value again would only find the same sums.
+        if first > 0 && sorted[first].0 == sorted[first - 1].0 {
+            continue;
+        }
+        let rest = &sorted[(first + 1)..];
+        let rest_target = match target.checked_sub(sorted[first].0) {
+            Some(rest_target) => rest_target,
+            None => continue,
+        };
+        if find_sorted(rest, k - 1, rest_target, chosen) {
+            for i in chosen.iter_mut() {
+                *i += first + 1;
+            }
+            chosen.push(first);
+            return true;
+        }
+    }
+    false
+}
+
+/// Find `k` distinct entries (by index) in `values` that sum to `target`.
+/// The values are sorted once, and the last two entries are found with two
+/// pointers, so the search takes O(n^(k-1)) time for k of at least 2.
+pub fn find_k_sum<T: Entry>(values: &[T], k: usize, target: T) -> Option<KSum<T>> {
+    let mut sorted: Vec<(i128, usize)> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| (x.into(), i))
+        .collect();
+    sorted.sort_unstable();
+    let mut chosen = Vec::new();
+    if !find_sorted(&sorted, k, target.into(), &mut chosen) {
+        return None;
+    }
+    let mut indices: Vec<usize> = chosen.iter().map(|&i| sorted[i].1).collect();
+    indices.sort_unstable();
+    Some(KSum {
+        entries: indices.iter().map(|&i| values[i]).collect(),
+        indices,
+    })
+}
+
+/// Parse one expense report entry per line.
+pub fn parse<T: Entry>(content: &str) -> Result<Vec<T>, ParseError> {
+    content
+        .lines()
+        .map(|x| parse_nr(content, x.trim()))
+        .collect()
+}
+
+/// Day 1 as a `Solution`, as used by the aoc runner.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Expenses<i64>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(parse(content)?.into_iter().collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        to_answer(part1(input, 2020))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        to_answer(part2(input, 2020))
+    }
+}
+
+// The product, or why there is none, as in "no pair sums to 2020".
+fn to_answer(result: Result<i64, SumError>) -> Answer {
+    match result {
+        Ok(product) => product.into(),
+        Err(e) => e.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expenses() {
+        let v: Expenses = [1721, 979, 366, 299, 675, 1456].iter().copied().collect();
+        assert_eq!(part1(&v, 2020), Ok(514579));
+        assert_eq!(part2(&v, 2020), Ok(241861950));
+        assert_eq!(v.find_all(2, 2020), vec![vec![299, 1721]]);
+
+        // A single 1010 can not be used twice, but two can.
+        let single: Expenses = [1010, 5, 7].iter().copied().collect();
+        assert_eq!(find_product(&single, 2020), Ok(None));
+        assert!(single.find_all(2, 2020).is_empty());
+        let double: Expenses = [1010, 5, 1010].iter().copied().collect();
+        assert_eq!(double.len(), 3);
+        assert_eq!(double.count(1010), 2);
+        assert_eq!(find_product(&double, 2020), Ok(Some(1010 * 1010)));
+        assert_eq!(double.find_all(2, 2020), vec![vec![1010, 1010]]);
+        assert!(double.find_all(3, 3030).is_empty());
+        // The only sums use a single entry twice.
+        let v: Expenses = [1, 4].iter().copied().collect();
+        assert!(v.find_all(3, 6).is_empty());
+        let v: Expenses = [1, 2, 10].iter().copied().collect();
+        assert!(v.find_all(3, 4).is_empty());
+        assert_eq!(v.find_all(3, 13), vec![vec![1, 2, 10]]);
+
+        let v: Expenses = [1, 2, 2, 3, 4, 4, 5].iter().copied().collect();
+        assert_eq!(v.find_all(2, 6), vec![vec![1, 5], vec![2, 4]]);
+        assert_eq!(
+            v.find_all(3, 9),
+            vec![vec![1, 3, 5], vec![1, 4, 4], vec![2, 2, 5], vec![2, 3, 4]]
+        );
+    }
+
+    #[test]
+    fn test_not_found() {
+        let v = [1, 2, 3];
+        let h: Expenses = v.iter().copied().collect();
+        let error = part1(&h, 2020).unwrap_err();
+        assert_eq!(error.to_string(), "no pair sums to 2020");
+        assert_eq!(part1_simple(&v, 2020), Err(error));
+        let error = part2(&h, 2020).unwrap_err();
+        assert_eq!(error.to_string(), "no triple sums to 2020");
+        assert_eq!(part2_simple(&v, 2020), Err(error));
+        assert!(part2_simple(&[2020], 2020).is_err());
+
+        let input = Day1::parse("1\n2\n3").unwrap();
+        assert_eq!(Day1::part2(&input), Answer::from("no triple sums to 2020"));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let v = [100000, -97980, 5];
+        let narrow: Expenses<i32> = v.iter().copied().collect();
+        let error = part1(&narrow, 2020).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "product of -97980 * 100000 does not fit in i32"
+        );
+        assert!(part1_simple(&v, 2020).is_err());
+        let wide: Expenses<i64> = v.iter().map(|&x| x as i64).collect();
+        assert_eq!(part1(&wide, 2020), Ok(-9798000000));
+
+        let (a, b) = (1i64 << 40, 1i64 << 41);
+        let v = [a, b, 2020 - a - b, 7];
+        let narrow: Expenses<i64> = v.iter().copied().collect();
+        assert!(part2(&narrow, 2020).is_err());
+        let wide: Expenses<i128> = v.iter().map(|&x| x as i128).collect();
+        let expected = a as i128 * b as i128 * (2020 - a - b) as i128;
+        assert_eq!(part2(&wide, 2020), Ok(expected));
+        assert_eq!(
+            find_k_sum(&[i128::MAX, i128::MAX, 1, 2], 2, 3)
+                .unwrap()
+                .indices,
+            vec![2, 3]
+        );
+        assert_eq!(product(&[i128::MAX, 2]).unwrap_err().type_name, "i128");
+    }
+
+    #[test]
+    fn test_find_k_sum() {
+        let v = [1721, 979, 366, 299, 675, 1456];
+        let pair = find_k_sum(&v, 2, 2020).unwrap();
+        assert_eq!(pair.entries, vec![1721, 299]);
+        assert_eq!(pair.indices, vec![0, 3]);
+        let triple = find_k_sum(&v, 3, 2020).unwrap();
+        assert_eq!(triple.entries, vec![979, 366, 675]);
+        assert_eq!(triple.indices, vec![1, 2, 4]);
+        let four = find_k_sum(&v, 4, 979 + 366 + 299 + 1456).unwrap();
+        assert_eq!(four.indices, vec![1, 2, 3, 5]);
+        assert_eq!(
+            find_k_sum(&v, 5, 1721 + 979 + 366 + 299 + 675)
+                .unwrap()
+                .indices
+                .len(),
+            5
+        );
+        assert_eq!(find_k_sum(&v, 0, 0).unwrap().indices, vec![]);
+        assert_eq!(find_k_sum(&v, 2, 2), None);
+        assert_eq!(find_k_sum(&v, 7, 2020), None);
+        // Equal values are still different entries.
+        assert_eq!(
+            find_k_sum(&[5, 1010, 1010], 2, 2020).unwrap().indices,
+            vec![1, 2]
+        );
+    }
+}
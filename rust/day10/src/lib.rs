@@ -0,0 +1,139 @@
+//! Solutions to 2020: Advent of Code day 10
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+/// Number of 1-jolt differences multiplied by the number of 3-jolt
+/// differences, when using all adapters.
+pub fn part1(v: &[i32]) -> i32 {
+    let mut sorted: Vec<i32> = v.to_owned();
+    sorted.sort();
+    sorted.push(sorted.last().unwrap() + 3);
+
+    let mut cur = 0;
+    let mut m: HashMap<i32, i32> = HashMap::new();
+    for x in sorted {
+        let diff = x - cur;
+        *m.entry(diff).or_insert(0) += 1;
+        cur = x;
+    }
+    m[&1] * m[&3]
+}
+
+/// Number of distinct arrangements of adapters that connect the outlet to
+/// the device.
+pub fn part2(v: &[i32]) -> i64 {
+    let mut sorted: Vec<i32> = v.to_owned();
+    sorted.push(0);
+    sorted.sort();
+    let last_value = sorted.last().unwrap() + 3;
+    sorted.push(last_value);
+
+    let mut m: HashMap<i32, i64> = HashMap::new();
+    for x in &sorted {
+        m.insert(*x, 0);
+    }
+    *m.get_mut(&0).unwrap() = 1;
+
+    for x in sorted {
+        let this_count = m[&x];
+        for i in 1..=3 {
+            let next = x + i;
+            if let Some(entry) = m.get_mut(&next) {
+                *entry += this_count;
+            }
+        }
+    }
+
+    m[&last_value]
+}
+
+/// Parse one adapter joltage per line.
+pub fn parse(content: &str) -> Result<Vec<i32>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|x| parse_nr(content, x.trim()))
+        .collect()
+}
+
+/// Day 10 as a `Solution`, as used by the aoc runner.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Vec<i32>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "16
+        10
+        15
+        5
+        1
+        11
+        7
+        19
+        6
+        12
+        4";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 35);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "28
+        33
+        18
+        42
+        31
+        14
+        46
+        20
+        48
+        47
+        24
+        23
+        49
+        45
+        19
+        38
+        39
+        11
+        1
+        32
+        25
+        35
+        8
+        17
+        7
+        9
+        4
+        2
+        34
+        10
+        3";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 19208);
+    }
+}
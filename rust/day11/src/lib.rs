@@ -0,0 +1,169 @@
+//! Solutions to 2020: Advent of Code day 11
+//! By Peter Fornwall
+
+use common::{parse_grid, Answer, ParseError, Solution};
+
+fn count_occupied(
+    floor_map: &[Vec<u8>],
+    x_check: usize,
+    y_check: usize,
+    check_line: bool,
+) -> usize {
+    let width = floor_map[0].len();
+    let height = floor_map.len();
+    let mut result = 0;
+    for delta_y in -1..=1 {
+        for delta_x in -1..=1 {
+            if delta_x == 0 && delta_y == 0 {
+                continue;
+            }
+            let seen = false;
+            let mut cur_x = x_check as i32;
+            let mut cur_y = y_check as i32;
+            loop {
+                cur_x += delta_x;
+                cur_y += delta_y;
+                if cur_x < 0 || cur_x >= width as i32 || cur_y < 0 || cur_y >= height as i32 {
+                    break;
+                }
+                let item = floor_map[cur_y as usize][cur_x as usize];
+                if item == b'#' {
+                    result += 1;
+                    break;
+                } else if !check_line || item == b'L' {
+                    break;
+                }
+            }
+            if seen {
+                result += 1;
+            }
+        }
+    }
+    result
+}
+
+fn find_occupied_ferry_seats(
+    input_map: &[Vec<u8>],
+    check_lines: bool,
+    nr_neighbours_to_count: usize,
+) -> usize {
+    let width = input_map[0].len();
+    let height = input_map.len();
+    let mut maps: [Vec<Vec<u8>>; 2] = [input_map.to_owned(), input_map.to_owned()];
+
+    let mut iterations = 0;
+    loop {
+        iterations += 1;
+        let mut changed = false;
+        let source_index = iterations % 2;
+        let dest_index = (iterations + 1) % 2;
+        for y in 0..height {
+            for x in 0..width {
+                let ch = maps[source_index][y][x];
+                if ch != b'.' {
+                    let nr = count_occupied(&maps[source_index], x, y, check_lines);
+                    if ch == b'L' {
+                        if nr == 0 {
+                            maps[dest_index][y][x] = b'#';
+                            changed = true;
+                        } else {
+                            maps[dest_index][y][x] = b'L';
+                        }
+                    } else if ch == b'#' {
+                        if nr >= nr_neighbours_to_count {
+                            maps[dest_index][y][x] = b'L';
+                            changed = true;
+                        } else {
+                            maps[dest_index][y][x] = b'#';
+                        }
+                    }
+                }
+            }
+        }
+        if !changed {
+            let result = maps[dest_index]
+                .iter()
+                .flatten()
+                .filter(|x| **x == b'#')
+                .count();
+            return result;
+        }
+    }
+}
+
+/// Occupied seats when the seating stabilizes, looking at adjacent seats.
+pub fn part1(input_map: &[Vec<u8>]) -> usize {
+    find_occupied_ferry_seats(input_map, false, 4)
+}
+
+/// Occupied seats when the seating stabilizes, looking at the first seat in
+/// each direction.
+pub fn part2(input_map: &[Vec<u8>]) -> usize {
+    find_occupied_ferry_seats(input_map, true, 5)
+}
+
+/// Parse the seat layout, keeping the `L`, `#` and `.` cells as bytes.
+pub fn parse(content: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    parse_grid(content, |c| match c {
+        'L' | '.' | '#' => Some(c as u8),
+        _ => None,
+    })
+}
+
+/// Day 11 as a `Solution`, as used by the aoc runner.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Vec<Vec<u8>>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "L.LL.LL.LL
+        LLLLLLL.LL
+        L.L.L..L..
+        LLLL.LL.LL
+        L.LL.LL.LL
+        L.LLLLL.LL
+        ..L.L.....
+        LLLLLLLLLL
+        L.LLLLLL.L
+        L.LLLLL.LL";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 37);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "L.LL.LL.LL
+        LLLLLLL.LL
+        L.L.L..L..
+        LLLL.LL.LL
+        L.LL.LL.LL
+        L.LLLLL.LL
+        ..L.L.....
+        LLLLLLLLLL
+        L.LLLLLL.L
+        L.LLLLL.LL";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 26);
+    }
+}
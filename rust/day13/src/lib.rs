@@ -0,0 +1,159 @@
+//! Solutions to 2020: Advent of Code day 13
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use modinverse::modinverse;
+
+/// ID of the earliest bus after `timestamp`, multiplied by the wait. Buses
+/// out of service are -1 in `bus_table`.
+pub fn part1(timestamp: i64, bus_table: &[i64]) -> i64 {
+    let mut min_wait = i64::MAX;
+    let mut result = i64::MAX;
+
+    for bus in bus_table {
+        if *bus != -1 {
+            let mut wait = timestamp % bus;
+            if wait != 0 {
+                wait = bus - wait;
+            }
+            if wait < min_wait {
+                min_wait = wait;
+                result = min_wait * bus;
+            }
+        }
+    }
+    result
+}
+
+/// Smallest non-negative x with x = remainder (mod modulus) for every
+/// (remainder, modulus) in `congruences`, by the Chinese remainder theorem.
+/// None if the moduli are not pairwise coprime.
+/// See e.g.: <https://en.wikipedia.org/wiki/Chinese_remainder_theorem>
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<i64> {
+    // All x = step * t + result (for any integer t) solve the congruences so far.
+    let mut result: i64 = 0;
+    let mut step: i64 = 1;
+    for &(remainder, modulus) in congruences {
+        // Find t so that:
+        //    step * t + result = remainder (mod modulus)
+        //    t = (1/step)_mod(modulus) * (remainder - result)  (mod modulus)
+        let inverse = modinverse(step.rem_euclid(modulus), modulus)?;
+        let t = (remainder - result).rem_euclid(modulus) * inverse % modulus;
+        result += step * t;
+        step *= modulus;
+    }
+    Some(result)
+}
+
+/// Earliest timestamp t where each bus in `bus_table` leaves at t plus its
+/// index. This solution works, since bus numbers are prime.
+pub fn part2(bus_table: &[i64]) -> i64 {
+    // Bus bus_nr leaves at t + bus_delay, so:
+    //    t + bus_delay = 0 (mod bus_nr)
+    let congruences: Vec<(i64, i64)> = bus_table
+        .iter()
+        .enumerate()
+        .filter(|(_, &bus_nr)| bus_nr != -1)
+        .map(|(bus_delay, &bus_nr)| (-(bus_delay as i64), bus_nr))
+        .collect();
+    chinese_remainder(&congruences).expect("no inverse, not a prime bus!!! ;)")
+}
+
+/// Parse the earliest timestamp and the bus table, with `x` as -1.
+pub fn parse(content: &str) -> Result<(i64, Vec<i64>), ParseError> {
+    let mut lines = content.trim().lines();
+    let t_str = lines.next().unwrap_or("").trim();
+    let t = parse_nr(content, t_str)?;
+    let v = lines
+        .next()
+        .ok_or_else(|| ParseError::after(content, t_str, "missing bus table"))?
+        .trim()
+        .split(',')
+        .map(|x| match x {
+            "x" => Ok(-1),
+            _ => match parse_nr(content, x)? {
+                nr if nr > 0 => Ok(nr),
+                _ => Err(ParseError::at(content, x, "bus number must be positive")),
+            },
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((t, v))
+}
+
+/// Day 13 as a `Solution`, as used by the aoc runner.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = (i64, Vec<i64>);
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input.0, &input.1).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(&input.1).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "939
+        7,13,x,x,59,x,31,19";
+        let v = parse(input).unwrap();
+        let result = part1(v.0, &v.1);
+        assert_eq!(result, 295);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "939
+        7,13,x,x,59,x,31,19";
+        let v = parse(input).unwrap();
+        let result = part2(&v.1);
+        assert_eq!(result, 1068781);
+
+        let input = "234
+        17,x,13,19";
+        let v = parse(input).unwrap();
+        let result = part2(&v.1);
+        assert_eq!(result, 3417);
+
+        let input = "234
+        67,7,59,61";
+        let v = parse(input).unwrap();
+        let result = part2(&v.1);
+        assert_eq!(result, 754018);
+
+        let input = "234
+        67,x,7,59,61";
+        let v = parse(input).unwrap();
+        let result = part2(&v.1);
+        assert_eq!(result, 779210);
+
+        let input = "234
+        67,7,x,59,61";
+        let v = parse(input).unwrap();
+        let result = part2(&v.1);
+        assert_eq!(result, 1261476);
+
+        let input = "234
+        1789,37,47,1889";
+        let v = parse(input).unwrap();
+        let result = part2(&v.1);
+        assert_eq!(result, 1202161486);
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(chinese_remainder(&[(0, 3), (3, 4), (4, 5)]), Some(39));
+        assert_eq!(chinese_remainder(&[(0, 4), (1, 6)]), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Solutions to 2020: Advent of Code day 14
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+/// A program line. The mask is stored as a bit mask of the `X` positions and
+/// the value of the other bits.
+pub enum Cmd {
+    // mask_x, mask_nr
+    Mask(i64, i64),
+    // addr, val
+    Set(i64, i64),
+}
+
+/// Sum of memory, when the mask is applied to the values.
+pub fn part1(v: &[Cmd]) -> i64 {
+    let mut m: HashMap<i64, i64> = HashMap::new();
+    let mut mask_x = 0;
+    let mut mask_nr = 0;
+    for cmd in v {
+        match cmd {
+            Cmd::Mask(new_mask_x, new_mask_nr) => {
+                mask_x = *new_mask_x;
+                mask_nr = *new_mask_nr;
+            }
+            Cmd::Set(addr, val) => {
+                // mask_x is what bits to keep, mask_nr what bits to set
+                let nr = mask_nr + (val & mask_x);
+                m.insert(*addr, nr);
+            }
+        }
+    }
+
+    m.values().sum()
+}
+
+fn recursive_set(m: &mut HashMap<i64, i64>, mask_x: i64, addr: i64, val: i64, bit: i64) {
+    if bit == 36 {
+        m.insert(addr, val);
+        return;
+    }
+    recursive_set(m, mask_x, addr, val, bit + 1);
+    let this_bit = mask_x & (1 << bit);
+    if this_bit != 0 {
+        recursive_set(m, mask_x, addr + this_bit, val, bit + 1);
+    }
+}
+
+/// Sum of memory, when the mask is applied to the addresses.
+pub fn part2(v: &[Cmd]) -> i64 {
+    let mut m: HashMap<i64, i64> = HashMap::new();
+    let mut mask_x = 0;
+    let mut mask_nr = 0;
+    for cmd in v {
+        match cmd {
+            Cmd::Mask(new_mask_x, new_mask_nr) => {
+                mask_x = *new_mask_x;
+                mask_nr = *new_mask_nr;
+            }
+            Cmd::Set(addr, val) => {
+                let addr_not_floating = (addr | mask_nr) & (!mask_x);
+                recursive_set(&mut m, mask_x, addr_not_floating, *val, 0);
+            }
+        }
+    }
+
+    m.values().sum()
+}
+
+/// Parse lines like `mask = X1X0` and `mem[8] = 11`.
+pub fn parse(content: &str) -> Result<Vec<Cmd>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|x| {
+            let mut temp = x.split('=');
+            let x1 = temp.next().unwrap_or(x).trim();
+            let x2 = temp
+                .next()
+                .ok_or_else(|| ParseError::after(content, x, "expected '='"))?
+                .trim();
+            if x1.starts_with("mem[") && x1.ends_with(']') {
+                let num1: i64 = parse_nr(content, &x1[4..(x1.len() - 1)])?;
+                let num2: i64 = parse_nr(content, x2)?;
+                Ok(Cmd::Set(num1, num2))
+            } else if x1 == "mask" {
+                if x2.len() != 36 {
+                    return Err(ParseError::at(content, x2, "mask must have 36 bits"));
+                }
+                let mut bitty: i64 = 1;
+                let mut mask_nr: i64 = 0;
+                let mut mask_x: i64 = 0;
+                for (index, i) in x2.char_indices().rev() {
+                    if i == 'X' {
+                        mask_x |= bitty;
+                    } else if i == '1' {
+                        mask_nr |= bitty;
+                    } else if i != '0' {
+                        return Err(ParseError::at(
+                            content,
+                            &x2[index..],
+                            format!("unexpected mask bit '{}'", i),
+                        ));
+                    }
+                    bitty <<= 1;
+                }
+                Ok(Cmd::Mask(mask_x, mask_nr))
+            } else {
+                Err(ParseError::at(content, x1, "expected mask or mem[]"))
+            }
+        })
+        .collect()
+}
+
+/// Day 14 as a `Solution`, as used by the aoc runner.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = Vec<Cmd>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+        mem[8] = 11
+        mem[7] = 101
+        mem[8] = 0";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 165);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "mask = 000000000000000000000000000000X1001X
+        mem[42] = 100
+        mask = 00000000000000000000000000000000X0XX
+        mem[26] = 1";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 208);
+    }
+}
@@ -0,0 +1,94 @@
+//! Solutions to 2020: Advent of Code day 15
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+
+fn get_number(start_seq: &[usize], nr_iterations: usize) -> usize {
+    if nr_iterations <= start_seq.len() {
+        return start_seq[nr_iterations - 1];
+    }
+    // Numbers spoken after the starting ones are below `nr_iterations`, but
+    // the starting numbers can be larger.
+    let max_start = start_seq.iter().copied().max().unwrap_or(0);
+    let mut last_seen = vec![usize::MAX; nr_iterations.max(max_start + 1)];
+    for (index, &value) in start_seq[..(start_seq.len() - 1)].iter().enumerate() {
+        last_seen[value] = index;
+    }
+    let mut last_number = *start_seq.last().unwrap();
+
+    for i in (start_seq.len() - 1)..(nr_iterations - 1) {
+        let last_index = last_seen[last_number];
+        last_seen[last_number] = i;
+        if last_index != usize::MAX {
+            last_number = i - last_index;
+        } else {
+            last_number = 0;
+        }
+    }
+    last_number
+}
+
+/// The 2020th number spoken.
+pub fn part1(v: &[usize]) -> usize {
+    get_number(v, 2020)
+}
+
+/// The 30000000th number spoken.
+pub fn part2(v: &[usize]) -> usize {
+    get_number(v, 30_000_000)
+}
+
+/// Parse the comma separated starting numbers.
+pub fn parse(content: &str) -> Result<Vec<usize>, ParseError> {
+    content
+        .trim()
+        .split(',')
+        .map(|x| parse_nr(content, x.trim()))
+        .collect()
+}
+
+/// Day 15 as a `Solution`, as used by the aoc runner.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<usize>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let v = vec![1, 3, 2];
+        let result = part1(&v);
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_large_start() {
+        assert_eq!(get_number(&[5000, 1], 3), 0);
+        assert_eq!(get_number(&[5000, 1], 5), 1);
+        assert_eq!(part1(&[5000, 1]), get_number(&[5001, 1], 2020));
+        assert_eq!(get_number(&[7, 8, 9], 2), 8);
+    }
+
+    #[test]
+    fn test_part2() {
+        let v = vec![0, 3, 6];
+        let result = part2(&v);
+        assert_eq!(result, 175594);
+    }
+}
@@ -0,0 +1,271 @@
+//! Solutions to 2020: Advent of Code day 16
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+struct Range {
+    min: i32,
+    max: i32,
+}
+
+struct Field {
+    name: String,
+    ranges: Vec<Range>,
+}
+
+/// The ticket fields with their valid ranges, your ticket and the nearby
+/// tickets.
+pub struct Data {
+    fields: Vec<Field>,
+    my_ticket: Vec<i32>,
+    nearby_tickets: Vec<Vec<i32>>,
+}
+
+fn is_value_in_field_ranges(field: &Field, value: i32) -> bool {
+    for range in &field.ranges {
+        if value >= range.min && value <= range.max {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_value_in_any_valid_ranges(data: &Data, value: i32) -> bool {
+    for field in &data.fields {
+        if is_value_in_field_ranges(field, value) {
+            return true;
+        }
+    }
+    false
+}
+
+fn get_valid_tickets_and_checksum(data: &Data) -> (Vec<Vec<i32>>, i32) {
+    let mut valid_nearby_tickets: Vec<Vec<i32>> = Vec::new();
+    let mut checksum = 0;
+
+    for nearby in &data.nearby_tickets {
+        let mut all_valid = true;
+        for &nearby_value in nearby {
+            if !is_value_in_any_valid_ranges(data, nearby_value) {
+                all_valid = false;
+                checksum += nearby_value;
+                continue;
+            }
+        }
+        if all_valid {
+            valid_nearby_tickets.push(nearby.clone());
+        }
+    }
+    (valid_nearby_tickets, checksum)
+}
+
+/// Sum of the values on nearby tickets that are not valid for any field.
+pub fn part1(data: &Data) -> i32 {
+    let (_, checksum) = get_valid_tickets_and_checksum(data);
+    checksum
+}
+
+/// Product of the departure fields on your ticket, and the field names in
+/// ticket order.
+pub fn part2(data: &Data) -> (i64, Vec<String>) {
+    let (valid_nearby_tickets, _) = get_valid_tickets_and_checksum(data);
+
+    let mut ticket_product = 1i64;
+    let mut return_fields = Vec::new();
+    let mut seen_field_ids = HashSet::new();
+    let nr_fields = data.fields.len();
+    while seen_field_ids.len() != nr_fields {
+        for ticket_field_index in 0..nr_fields {
+            let mut fields_ok_count = 0;
+            let mut could_be_field_index = 0;
+            for check_field_index in 0..nr_fields {
+                if seen_field_ids.contains(&check_field_index) {
+                    continue;
+                }
+                let all_valid = valid_nearby_tickets.iter().all(|tickets| {
+                    is_value_in_field_ranges(
+                        &data.fields[check_field_index],
+                        tickets[ticket_field_index],
+                    )
+                });
+                if all_valid {
+                    fields_ok_count += 1;
+                    if fields_ok_count > 1 {
+                        break;
+                    }
+                    could_be_field_index = check_field_index;
+                }
+            }
+            if fields_ok_count == 1 {
+                let field_name = &data.fields[could_be_field_index].name;
+                if field_name.starts_with("departure") {
+                    let value = data.my_ticket[ticket_field_index] as i64;
+                    ticket_product *= value;
+                }
+                return_fields.push(field_name.clone());
+                seen_field_ids.insert(could_be_field_index);
+            }
+        }
+    }
+    (ticket_product, return_fields)
+}
+
+fn parse_ticket(content: &str, s: &str) -> Result<Vec<i32>, ParseError> {
+    s.trim()
+        .split(',')
+        .map(|x| parse_nr(content, x.trim()))
+        .collect()
+}
+
+// Parse a ticket section: a header line followed by one ticket per line, each
+// with one value per field.
+fn parse_tickets(
+    content: &str,
+    section: &str,
+    header: &str,
+    nr_fields: usize,
+) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut lines = section.trim().split('\n').map(|x| x.trim());
+    let first = lines.next().unwrap_or(section);
+    if first != header {
+        return Err(ParseError::at(
+            content,
+            first,
+            format!("expected '{}'", header),
+        ));
+    }
+    let mut tickets = Vec::new();
+    for line in lines {
+        let ticket = parse_ticket(content, line)?;
+        if ticket.len() != nr_fields {
+            return Err(ParseError::at(
+                content,
+                line,
+                format!("expected {} values, found {}", nr_fields, ticket.len()),
+            ));
+        }
+        tickets.push(ticket);
+    }
+    Ok(tickets)
+}
+
+/// Parse the three sections of the notes, separated by blank lines.
+pub fn parse(content: &str) -> Result<Data, ParseError> {
+    let sections: Vec<&str> = content.trim().split("\n\n").collect();
+    if sections.len() != 3 {
+        return Err(ParseError::after(
+            content,
+            content.trim_end(),
+            format!("expected 3 sections, found {}", sections.len()),
+        ));
+    }
+    let mut fields: Vec<Field> = Vec::new();
+    for line in sections[0].trim().split('\n') {
+        // departure location: 32-69 or 86-968
+        let line = line.trim();
+        let mut parts = line.split(':');
+        let name = String::from(parts.next().unwrap_or(line).trim());
+        let ranges_str = parts
+            .next()
+            .ok_or_else(|| ParseError::after(content, line, "expected ':'"))?;
+        let mut ranges: Vec<Range> = Vec::new();
+        for range_str in ranges_str.trim().split("or") {
+            let range_str = range_str.trim();
+            let mut range_it = range_str.split('-');
+            let min = parse_nr(content, range_it.next().unwrap_or(range_str))?;
+            let max_str = range_it
+                .next()
+                .ok_or_else(|| ParseError::after(content, range_str, "expected '-'"))?;
+            let max = parse_nr(content, max_str)?;
+            ranges.push(Range { min, max });
+        }
+        let field = Field { name, ranges };
+        fields.push(field);
+    }
+
+    let nr_fields = fields.len();
+    let my_tickets = parse_tickets(content, sections[1], "your ticket:", nr_fields)?;
+    let my_ticket = match my_tickets.len() {
+        1 => my_tickets[0].clone(),
+        _ => {
+            return Err(ParseError::at(
+                content,
+                sections[1],
+                "expected exactly one ticket",
+            ))
+        }
+    };
+    let nearby_tickets = parse_tickets(content, sections[2], "nearby tickets:", nr_fields)?;
+
+    Ok(Data {
+        fields,
+        my_ticket,
+        nearby_tickets,
+    })
+}
+
+/// Day 16 as a `Solution`, as used by the aoc runner.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Data;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).0.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "class: 1-3 or 5-7
+        row: 6-11 or 33-44
+        seat: 13-40 or 45-50
+
+        your ticket:
+        7,1,14
+
+        nearby tickets:
+        7,3,47
+        40,4,50
+        55,2,20
+        38,6,12";
+        let data = parse(input).unwrap();
+        let result = part1(&data);
+        assert_eq!(result, 71);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "class: 0-1 or 4-19
+        row: 0-5 or 8-19
+        seat: 0-13 or 16-19
+
+        your ticket:
+        11,12,13
+
+        nearby tickets:
+        3,9,18
+        15,1,5
+        5,14,9";
+        let data = parse(input).unwrap();
+        let (result, fields) = part2(&data);
+        assert_eq!(result, 1);
+        assert_eq!(
+            fields,
+            vec!["row".to_string(), "class".to_string(), "seat".to_string()]
+        );
+    }
+}
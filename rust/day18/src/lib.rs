@@ -0,0 +1,248 @@
+//! Solutions to 2020: Advent of Code day 18
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum OperationKind {
+    Add,
+    Mul,
+    //None,
+}
+#[derive(Debug, Copy, Clone)]
+enum Node {
+    Number(i64),
+    Operation(OperationKind),
+}
+
+impl Node {
+    fn get_expected_number(&self) -> i64 {
+        match self {
+            Node::Number(nr) => *nr,
+            _ => panic!(),
+        }
+    }
+
+    fn get_expected_op_kind(&self) -> OperationKind {
+        match self {
+            Node::Operation(op_kind) => *op_kind,
+            _ => panic!(),
+        }
+    }
+}
+
+// if do_op_kind == None, then perform both addition/multiplication in order.
+fn calc_loop(nodes: &mut Vec<Node>, do_op_kind: Option<OperationKind>) {
+    let mut i = 0;
+    while (i + 2) < nodes.len() {
+        let op_kind = nodes[i + 1].get_expected_op_kind();
+        if do_op_kind.is_none() || do_op_kind == Some(op_kind) {
+            let nr1 = nodes[i].get_expected_number();
+            let nr2 = nodes[i + 2].get_expected_number();
+            let result = if op_kind == OperationKind::Add {
+                nr1 + nr2
+            } else {
+                nr1 * nr2
+            };
+            nodes[i] = Node::Number(result);
+            nodes.remove(i + 1);
+            nodes.remove(i + 1);
+        } else {
+            i += 2;
+        }
+    }
+}
+
+/// Operator precedence used by `evaluate`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Precedence {
+    /// Addition and multiplication have the same precedence, as in part 1.
+    Same,
+    /// Addition is evaluated before multiplication, as in part 2.
+    AdditionFirst,
+}
+
+fn calc(nodes: &[Node], precedence: Precedence) -> i64 {
+    let mut nodes = nodes.to_vec();
+    match precedence {
+        // Addition/Multiplication have the same precedence. Do both in order.
+        Precedence::Same => calc_loop(&mut nodes, None),
+        // Addition is evaluated before multiplication.
+        Precedence::AdditionFirst => {
+            calc_loop(&mut nodes, Some(OperationKind::Add));
+            calc_loop(&mut nodes, Some(OperationKind::Mul));
+        }
+    }
+    nodes[0].get_expected_number()
+}
+
+fn token_regex() -> Regex {
+    Regex::new(r"\d+|\(|\)|\+|\*").unwrap()
+}
+
+fn evaluate_tokens(re: &Regex, expression: &str, precedence: Precedence) -> i64 {
+    let mut input_items: Vec<&str> = re.find_iter(expression).map(|x| x.as_str()).collect();
+    input_items.insert(0, "(");
+    input_items.push(")");
+
+    let mut parenthesis_stack: Vec<Vec<Node>> = Vec::new();
+    parenthesis_stack.push(Vec::new());
+    for item in input_items {
+        if item == "(" {
+            parenthesis_stack.push(Vec::new());
+        } else if item == ")" {
+            let nodes = parenthesis_stack.last().unwrap();
+            let result = calc(nodes, precedence);
+            parenthesis_stack.pop();
+            let parent_nodes = parenthesis_stack.last_mut().unwrap();
+            parent_nodes.push(Node::Number(result));
+        } else if item == "+" || item == "*" {
+            let op_kind = if item == "+" {
+                OperationKind::Add
+            } else {
+                OperationKind::Mul
+            };
+            let nodes = parenthesis_stack.last_mut().unwrap();
+            nodes.push(Node::Operation(op_kind));
+        } else {
+            let nr = item.parse::<i64>().unwrap();
+            let nodes = parenthesis_stack.last_mut().unwrap();
+            nodes.push(Node::Number(nr));
+        }
+    }
+    parenthesis_stack[0][0].get_expected_number()
+}
+
+/// Evaluate an expression of numbers, `+`, `*` and parentheses. Errors are
+/// located in `expression`.
+pub fn evaluate(expression: &str, precedence: Precedence) -> Result<i64, ParseError> {
+    check_expression(expression, expression)?;
+    Ok(evaluate_tokens(&token_regex(), expression, precedence))
+}
+
+fn sum_expressions(expressions: &[&str], precedence: Precedence) -> i64 {
+    let re = token_regex();
+    expressions
+        .iter()
+        .map(|x| evaluate_tokens(&re, x, precedence))
+        .sum()
+}
+
+/// Sum of the expressions, with the same precedence for `+` and `*`.
+pub fn part1(expressions: &[&str]) -> i64 {
+    sum_expressions(expressions, Precedence::Same)
+}
+
+/// Sum of the expressions, with `+` evaluated before `*`.
+pub fn part2(expressions: &[&str]) -> i64 {
+    sum_expressions(expressions, Precedence::AdditionFirst)
+}
+
+// Check that an expression only has numbers, additions, multiplications and
+// balanced parentheses, in an order that can be evaluated.
+fn check_expression<'a>(content: &str, line: &'a str) -> Result<&'a str, ParseError> {
+    let mut depth = 0;
+    let mut expect_number = true;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            ' ' => {}
+            '(' if expect_number => depth += 1,
+            ')' if !expect_number && depth > 0 => depth -= 1,
+            '+' | '*' if !expect_number => expect_number = true,
+            '0'..='9' if expect_number => {
+                let mut end = i + 1;
+                while let Some(&(j, '0'..='9')) = chars.peek() {
+                    end = j + 1;
+                    chars.next();
+                }
+                parse_nr::<i64>(content, &line[i..end])?;
+                expect_number = false;
+            }
+            _ => {
+                return Err(ParseError::at(
+                    content,
+                    &line[i..],
+                    format!("unexpected '{}'", ch),
+                ))
+            }
+        }
+    }
+    if expect_number || depth > 0 {
+        return Err(ParseError::after(content, line, "incomplete expression"));
+    }
+    Ok(line)
+}
+
+/// Parse one expression per line, checking that it can be evaluated.
+pub fn parse(content: &str) -> Result<Vec<&str>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|x| check_expression(content, x.trim()))
+        .collect()
+}
+
+/// Day 18 as a `Solution`, as used by the aoc runner.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "1 + 2 * 3 + 4 * 5 + 6";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 71);
+
+        let input = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 13632);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "1 + 2 * 3 + 4 * 5 + 6";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 231);
+
+        let input = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 23340);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let expression = "2 * 3 + (4 * 5)";
+        assert_eq!(evaluate(expression, Precedence::Same), Ok(26));
+        assert_eq!(evaluate(expression, Precedence::AdditionFirst), Ok(46));
+
+        let e = evaluate("2 * (3 +", Precedence::Same).unwrap_err();
+        assert_eq!((e.column, e.message.as_str()), (9, "incomplete expression"));
+        let e = evaluate("2 * + 3", Precedence::Same).unwrap_err();
+        assert_eq!((e.column, e.message.as_str()), (5, "unexpected '+'"));
+        assert!(evaluate("1 + 99999999999999999999", Precedence::Same).is_err());
+    }
+}
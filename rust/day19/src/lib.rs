@@ -0,0 +1,236 @@
+//! Solutions to 2020: Advent of Code day 19
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::{collections::HashMap, collections::HashSet};
+
+/// Map from rule number to its items, with alternatives separated by "|".
+pub type Rules = HashMap<String, Vec<String>>;
+
+// Get all end positions of matches for matching rule_nr at specified index in message
+fn get_match_ends(rules: &Rules, rule_nr: &str, message: &str, index: usize) -> HashSet<usize> {
+    let mut results: HashSet<usize> = HashSet::new();
+    if index >= message.len() {
+        return results;
+    }
+
+    // Rules 42 and 31 may be missing when part 2 is run on a part 1 example.
+    let this_rule = match rules.get(rule_nr) {
+        Some(rule) => rule,
+        None => return results,
+    };
+    if this_rule[0] == "a" || this_rule[0] == "b" {
+        if message[index..(index + 1)] == this_rule[0] {
+            results.insert(index + 1);
+        }
+    } else {
+        let mut is_first_in_or = true;
+        let mut possible_end_match: HashSet<usize> = HashSet::new();
+
+        for rule_item in this_rule.iter() {
+            if rule_item == "|" {
+                results.extend(&possible_end_match);
+                is_first_in_or = true;
+            } else if is_first_in_or {
+                possible_end_match = get_match_ends(rules, rule_item, message, index);
+                is_first_in_or = false;
+            } else {
+                let mut second_possible_end_match: HashSet<usize> = HashSet::new();
+                for x in possible_end_match {
+                    let checked_possible_end_match = get_match_ends(rules, rule_item, message, x);
+                    second_possible_end_match.extend(&checked_possible_end_match);
+                }
+                possible_end_match = second_possible_end_match;
+            }
+        }
+        results.extend(&possible_end_match);
+    }
+    results
+}
+
+/// Number of messages that completely match rule 0.
+pub fn sum_matches(rules: &Rules, messages: &[String]) -> usize {
+    let mut sum: usize = 0;
+    for message in messages {
+        let possible = get_match_ends(rules, "0", message, 0);
+        if possible.contains(&message.len()) {
+            sum += 1;
+        }
+    }
+    sum
+}
+
+// Replace rules 8 and 11 with the looping versions used in part 2.
+fn add_loop_rules(rules: &mut Rules) {
+    for (rule_nr, rule_def) in [("8", "42 | 42 8"), ("11", "42 31 | 42 11 31")].iter() {
+        let rule_def = rule_def.split(' ').map(|x| x.to_string()).collect();
+        rules.insert(rule_nr.to_string(), rule_def);
+    }
+}
+
+/// Parse the rules and the messages. With `is_part2`, rules 8 and 11 are
+/// replaced by the looping rules of part 2.
+pub fn parse(content: &str, is_part2: bool) -> Result<(Rules, Vec<String>), ParseError> {
+    let mut parts_it = content.trim().split("\n\n");
+    let rules_str = parts_it.next().unwrap_or("");
+    let messages_str = parts_it
+        .next()
+        .ok_or_else(|| ParseError::after(content, rules_str, "missing messages"))?;
+
+    let mut rules = HashMap::new();
+    let mut references: Vec<&str> = Vec::new();
+    for rule in rules_str.lines() {
+        let rule = rule.trim();
+        let mut rule_it = rule.split(": ");
+        let rule_nr = rule_it.next().unwrap_or(rule);
+        parse_nr::<usize>(content, rule_nr)?;
+        let rule_def_str = rule_it
+            .next()
+            .ok_or_else(|| ParseError::after(content, rule_nr, "expected ': '"))?;
+        if rule_def_str != "\"a\"" && rule_def_str != "\"b\"" {
+            for item in rule_def_str.split(' ').filter(|&x| x != "|") {
+                parse_nr::<usize>(content, item)?;
+                references.push(item);
+            }
+        }
+        let rule_def: Vec<String> = rule_def_str
+            .split(' ')
+            .map(|x| x.replace("\"", ""))
+            .collect();
+        rules.insert(rule_nr.to_string(), rule_def);
+    }
+    for item in references {
+        if !rules.contains_key(item) {
+            return Err(ParseError::at(content, item, format!("no rule {}", item)));
+        }
+    }
+    if !rules.contains_key("0") {
+        return Err(ParseError::at(content, rules_str, "missing rule 0"));
+    }
+    if is_part2 {
+        add_loop_rules(&mut rules);
+    }
+    let mut messages = Vec::new();
+    for message in messages_str.split('\n').map(|x| x.trim()) {
+        if let Some(i) = message.find(|c| c != 'a' && c != 'b') {
+            return Err(ParseError::at(
+                content,
+                &message[i..],
+                "messages may only contain 'a' and 'b'",
+            ));
+        }
+        messages.push(message.to_string());
+    }
+    Ok((rules, messages))
+}
+
+/// Day 19 as a `Solution`, as used by the aoc runner.
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = (Rules, Vec<String>);
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content, false)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        sum_matches(&input.0, &input.1).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        let mut rules = input.0.clone();
+        add_loop_rules(&mut rules);
+        sum_matches(&rules, &input.1).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = r#"0: 1 2
+        1: "a"
+        2: 1 3 | 3 1
+        3: "b"
+
+aa
+bb
+aab
+aba"#;
+        let (rules, messages) = parse(input, false).unwrap();
+        let result = sum_matches(&rules, &messages);
+        assert_eq!(result, 2);
+
+        let input = r#"0: 4 1 5
+        1: 2 3 | 3 2
+        2: 4 4 | 5 5
+        3: 4 5 | 5 4
+        4: "a"
+        5: "b"
+
+aaab
+ababbb
+aab
+aba"#;
+        let (rules, messages) = parse(input, false).unwrap();
+        let result = sum_matches(&rules, &messages);
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+        let (rules, messages) = parse(input, true).unwrap();
+        let result = sum_matches(&rules, &messages);
+        assert_eq!(result, 12);
+    }
+}
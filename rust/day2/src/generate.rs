@@ -0,0 +1,112 @@
+//! Random password databases, where each line is built to be valid or invalid
+//! under each of the two policies.
+
+use common::{Generated, Rng};
+
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.below(26) as u8) as char
+}
+
+// Build a password that is valid under the count policy if `valid1`, and under
+// the position policy if `valid2`. None if the policy can not be met.
+fn password(
+    rng: &mut Rng,
+    nr1: usize,
+    nr2: usize,
+    ch: char,
+    valid1: bool,
+    valid2: bool,
+) -> Option<String> {
+    let len = nr2 + rng.below(10);
+    let mut pwd: Vec<char> = (0..len)
+        .map(|_| loop {
+            let other = letter(rng);
+            if other != ch {
+                break other;
+            }
+        })
+        .collect();
+
+    let (at1, at2) = match (valid2, rng.chance(50)) {
+        (true, first) => (first, !first),
+        (false, both) => (both, both),
+    };
+    if at1 {
+        pwd[nr1 - 1] = ch;
+    }
+    if at2 {
+        pwd[nr2 - 1] = ch;
+    }
+
+    let placed = at1 as usize + at2 as usize;
+    let mut free: Vec<usize> = (0..len).filter(|&i| i != nr1 - 1 && i != nr2 - 1).collect();
+    let max_count = placed + free.len();
+    let (min, max) = if valid1 {
+        (nr1.max(placed), nr2.min(max_count))
+    } else if placed < nr1 && (rng.chance(50) || max_count <= nr2) {
+        (placed, nr1 - 1)
+    } else {
+        ((nr2 + 1).max(placed), max_count)
+    };
+    if min > max {
+        return None;
+    }
+    let count = rng.range(min as i64..=max as i64) as usize;
+    rng.shuffle(&mut free);
+    for &i in &free[..(count - placed)] {
+        pwd[i] = ch;
+    }
+    Some(pwd.into_iter().collect())
+}
+
+/// Database with `size` (at least 1) lines.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    let mut lines = Vec::new();
+    let mut nr_valid1 = 0;
+    let mut nr_valid2 = 0;
+    while lines.len() < size {
+        let nr1 = 1 + rng.below(8);
+        let nr2 = nr1 + 1 + rng.below(8);
+        let ch = letter(&mut rng);
+        let valid1 = rng.chance(50);
+        let valid2 = rng.chance(50);
+        if let Some(pwd) = password(&mut rng, nr1, nr2, ch, valid1, valid2) {
+            lines.push(format!("{}-{} {}: {}", nr1, nr2, ch, pwd));
+            nr_valid1 += valid1 as usize;
+            nr_valid2 += valid2 as usize;
+        }
+    }
+    Generated {
+        input: lines.join("\n"),
+        part1: nr_valid1.into(),
+        part2: nr_valid2.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day2;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let generated = generate(seed, 300);
+            let input = Day2::parse(&generated.input).unwrap();
+            assert_eq!(input.len(), 300);
+            assert_eq!(Day2::part1(&input), generated.part1);
+            assert_eq!(Day2::part2(&input), generated.part2);
+        }
+        for size in 0..3 {
+            let generated = generate(0, size);
+            // As printed by `aoc --generate`, with a newline at the end.
+            let input = Day2::parse(&format!("{}\n", generated.input)).unwrap();
+            assert_eq!(input.len(), size.max(1));
+            assert_eq!(Day2::part1(&input), generated.part1);
+            assert_eq!(Day2::part2(&input), generated.part2);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Solutions to 2020: Advent of Code day 2
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use policy::{CountPolicy, PasswordPolicy, PositionPolicy};
+
+pub mod generate;
+pub mod policy;
+pub mod report;
+
+/// A password and the policy it was set with.
+#[derive(Debug)]
+pub struct Password {
+    /// First number of the policy. Each policy decides which numbers it
+    /// accepts: positions start at 1, but a count can be 0.
+    pub nr1: usize,
+    /// Second number of the policy.
+    pub nr2: usize,
+    /// Character of the policy.
+    pub ch: char,
+    pub pwd: String,
+}
+
+// Parse a line like "1-7 q: qqqqxvqrkbqqztlqlzq"
+fn parse_line(content: &str, line: &str) -> Result<Password, ParseError> {
+    let mut it = line.split(' ');
+    let policy = it.next().unwrap_or(line);
+    let mut policy_it = policy.split('-');
+    let nr1: usize = parse_nr(content, policy_it.next().unwrap_or(policy))?;
+    let nr2_str = policy_it
+        .next()
+        .ok_or_else(|| ParseError::after(content, policy, "expected '-' in policy"))?;
+    let nr2: usize = parse_nr(content, nr2_str)?;
+    let ch_str = it
+        .next()
+        .ok_or_else(|| ParseError::after(content, policy, "missing policy character"))?;
+    let mut ch_it = ch_str.chars();
+    let ch = match (ch_it.next(), ch_it.next(), ch_it.next()) {
+        (Some(ch), Some(':'), None) => ch,
+        _ => {
+            return Err(ParseError::at(
+                content,
+                ch_str,
+                "expected a character and ':'",
+            ))
+        }
+    };
+    let pwd = it
+        .next()
+        .ok_or_else(|| ParseError::after(content, ch_str, "missing password"))?;
+    if let Some(extra) = it.next() {
+        return Err(ParseError::at(
+            content,
+            extra,
+            "unexpected text after password",
+        ));
+    }
+    Ok(Password {
+        nr1,
+        nr2,
+        ch,
+        pwd: String::from(pwd),
+    })
+}
+
+/// Number of passwords with between `nr1` and `nr2` of the policy character.
+pub fn part1(v: &[Password]) -> usize {
+    nr_valid(v, &CountPolicy)
+}
+
+/// Number of passwords with the policy character at exactly one of the
+/// positions `nr1` and `nr2` (1-based, in characters).
+pub fn part2(v: &[Password]) -> usize {
+    nr_valid(v, &PositionPolicy::default())
+}
+
+/// Number of passwords that follow `policy`.
+pub fn nr_valid(v: &[Password], policy: &dyn PasswordPolicy) -> usize {
+    v.iter()
+        .filter(|password| policy.check(password).is_ok())
+        .count()
+}
+
+/// Parse lines like `1-3 a: abcde`.
+pub fn parse(content: &str) -> Result<Vec<Password>, ParseError> {
+    content
+        .lines()
+        .map(|line| parse_line(content, line))
+        .collect()
+}
+
+/// Day 2 as a `Solution`, as used by the aoc runner.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<Password>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse() {
+        let input = "1-7 q: qqqqxvqrkbqqztlqlzq";
+        let p = &crate::parse(input).unwrap()[0];
+        assert_eq!(p.nr1, 1);
+        assert_eq!(p.nr2, 7);
+        assert_eq!(p.ch, 'q');
+        assert_eq!(p.pwd, String::from("qqqqxvqrkbqqztlqlzq"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "1-3 a: abcde\n1-x b: cdefg";
+        let e = crate::parse(input).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+}
@@ -1,68 +1,79 @@
 //! Solutions to 2020: Advent of Code day 2
 //! By Peter Fornwall
+//!
+//! Usage: day2 [INPUT] [--policy SPEC]... [--report text|csv|json]
+//!
+//! With --policy or --report, every line that breaks any of the policies is
+//! reported with the parsed fields and the reason, in the given format
+//! (default text), and the number of valid passwords is printed to stderr.
+//! Without --policy, the policies of part 1 and 2 are used. See
+//! `day2::policy::from_spec` for the policies.
 
-use std::fs;
+use day2::policy::{self, PasswordPolicy};
+use day2::report::{self, Format};
+use std::collections::HashSet;
+use std::env;
+use std::io;
+use std::process;
 
-struct Password {
-    nr1: usize,
-    nr2: usize,
-    ch: char,
-    pwd: String,
-}
-
-// Parse a line like "1-7 q: qqqqxvqrkbqqztlqlzq"
-fn parse(line: &str) -> Password {
-    let mut it = line.split(" ");
-    let policy: Vec<&str> = it.next().unwrap().split("-").collect();
-    let nr1: usize = policy[0].parse().unwrap();
-    let nr2: usize = policy[1].parse().unwrap();
-    let ch = it.next().unwrap().chars().next().unwrap();
-    let pwd = String::from(it.next().unwrap());
-    Password { nr1, nr2, ch, pwd }
-}
-
-fn part1(v: &Vec<Password>) {
-    let nr_passwords = v
-        .iter()
-        .filter(|password| {
-            let count = password.pwd.chars().filter(|x| *x == password.ch).count();
-            return count >= password.nr1 && count <= password.nr2;
-        })
-        .count();
-
-    println!("Part1, nr passwords: {}", nr_passwords);
-}
-
-fn part2(v: &Vec<Password>) {
-    let nr_passwords = v
-        .iter()
-        .filter(|password| {
-            let ch1 = password.pwd.as_bytes()[password.nr1 - 1] as char;
-            let ch2 = password.pwd.as_bytes()[password.nr2 - 1] as char;
-            return (ch1 == password.ch) ^ (ch2 == password.ch);
-        })
-        .count();
-
-    println!("Part2, nr passwords: {}", nr_passwords);
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: day2 [INPUT] [--policy SPEC]... [--report text|csv|json]");
+    process::exit(2);
 }
 
 fn main() {
-    let content = fs::read_to_string("../../inputs/day2_input.txt").expect("Cannot open file!");
-    let v: Vec<Password> = content.lines().map(|line| parse(line)).collect();
-
-    part1(&v);
-    part2(&v);
-}
+    let mut input = None;
+    let mut policies: Vec<Box<dyn PasswordPolicy>> = Vec::new();
+    let mut format = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| exit_with_usage(&format!("Missing value for {}", arg)))
+        };
+        match arg.as_str() {
+            "--policy" => {
+                let spec = value();
+                let policy = policy::from_spec(&spec).unwrap_or_else(|e| exit_with_usage(&e));
+                policies.push(policy);
+            }
+            "--report" => {
+                let name = value();
+                format = Some(Format::from_name(&name).unwrap_or_else(|| {
+                    exit_with_usage(&format!("Unknown report format: {}", name))
+                }));
+            }
+            _ => input = Some(arg),
+        }
+    }
+    let source = common::InputSource::from_arg(input.as_deref(), 2);
+    let content = common::read_or_exit(&source);
+    let v = common::unwrap_or_exit(day2::parse(&content), &source);
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn test_parse() {
-        let input = "1-7 q: qqqqxvqrkbqqztlqlzq";
-        let p = crate::parse(input);
-        assert_eq!(p.nr1, 1);
-        assert_eq!(p.nr2, 7);
-        assert_eq!(p.ch, 'q');
-        assert_eq!(p.pwd, String::from("qqqqxvqrkbqqztlqlzq"));
+    if policies.is_empty() && format.is_none() {
+        println!("Part1, nr passwords: {}", day2::part1(&v));
+        println!("Part2, nr passwords: {}", day2::part2(&v));
+        return;
+    }
+    if policies.is_empty() {
+        policies.push(Box::new(policy::CountPolicy));
+        policies.push(Box::new(policy::PositionPolicy::default()));
     }
+    let findings = report::audit(&v, &policies);
+    let stdout = io::stdout();
+    if let Err(e) = report::write_report(
+        &findings,
+        format.unwrap_or(Format::Text),
+        &mut stdout.lock(),
+    ) {
+        eprintln!("Cannot write report: {}", e);
+        process::exit(1);
+    }
+    let nr_invalid = findings
+        .iter()
+        .map(|finding| finding.line)
+        .collect::<HashSet<_>>()
+        .len();
+    eprintln!("Nr valid passwords: {}", v.len() - nr_invalid);
 }
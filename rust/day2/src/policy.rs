@@ -0,0 +1,348 @@
+//! Password policies, that can be combined and selected at runtime.
+
+use crate::Password;
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A rule that a password either follows, or breaks for a reason.
+pub trait PasswordPolicy {
+    /// Name of the policy, as used in `from_spec`.
+    fn name(&self) -> &str;
+
+    /// Ok if the password follows the policy, otherwise why it does not.
+    fn check(&self, password: &Password) -> Result<(), String>;
+}
+
+/// Between `nr1` and `nr2` of the policy character (the policy of part 1).
+pub struct CountPolicy;
+
+impl PasswordPolicy for CountPolicy {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn check(&self, password: &Password) -> Result<(), String> {
+        let count = password.pwd.chars().filter(|x| *x == password.ch).count();
+        if count >= password.nr1 && count <= password.nr2 {
+            Ok(())
+        } else {
+            Err(format!(
+                "'{}' occurs {} times, expected {} to {}",
+                password.ch, count, password.nr1, password.nr2
+            ))
+        }
+    }
+}
+
+/// What the positions of `PositionPolicy` count.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Unit {
+    /// Unicode scalar values, as in `str::chars`.
+    #[default]
+    Char,
+    /// Extended grapheme clusters, so that for example an `e` followed by a
+    /// combining accent is one position.
+    Grapheme,
+}
+
+/// The policy character at exactly one of the positions `nr1` and `nr2`
+/// (the policy of part 2). A position outside the password breaks the policy.
+#[derive(Default)]
+pub struct PositionPolicy {
+    pub unit: Unit,
+}
+
+impl PasswordPolicy for PositionPolicy {
+    fn name(&self) -> &str {
+        "position"
+    }
+
+    fn check(&self, password: &Password) -> Result<(), String> {
+        let mut ch = [0; 4];
+        let ch: &str = password.ch.encode_utf8(&mut ch);
+        let units: Vec<&str> = match self.unit {
+            Unit::Char => password
+                .pwd
+                .char_indices()
+                .map(|(i, x)| &password.pwd[i..(i + x.len_utf8())])
+                .collect(),
+            Unit::Grapheme => password.pwd.graphemes(true).collect(),
+        };
+        for &nr in &[password.nr1, password.nr2] {
+            if nr == 0 || nr > units.len() {
+                let unit = match self.unit {
+                    Unit::Char => "characters",
+                    Unit::Grapheme => "graphemes",
+                };
+                return Err(format!(
+                    "position {} is outside the {} {}",
+                    nr,
+                    units.len(),
+                    unit
+                ));
+            }
+        }
+        let ch1 = units[password.nr1 - 1];
+        let ch2 = units[password.nr2 - 1];
+        match (ch1 == ch, ch2 == ch) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(format!(
+                "'{}' at both positions {} and {}",
+                password.ch, password.nr1, password.nr2
+            )),
+            (false, false) => Err(format!(
+                "'{}' at neither position {} nor {}",
+                password.ch, password.nr1, password.nr2
+            )),
+        }
+    }
+}
+
+/// At least this many characters.
+pub struct MinLength(pub usize);
+
+impl PasswordPolicy for MinLength {
+    fn name(&self) -> &str {
+        "min-length"
+    }
+
+    fn check(&self, password: &Password) -> Result<(), String> {
+        let len = password.pwd.chars().count();
+        if len >= self.0 {
+            Ok(())
+        } else {
+            Err(format!("{} characters, expected at least {}", len, self.0))
+        }
+    }
+}
+
+/// None of the substrings, ignoring case.
+pub struct Forbidden(pub Vec<String>);
+
+impl PasswordPolicy for Forbidden {
+    fn name(&self) -> &str {
+        "forbid"
+    }
+
+    fn check(&self, password: &Password) -> Result<(), String> {
+        let pwd = password.pwd.to_lowercase();
+        match self.0.iter().find(|x| pwd.contains(&x.to_lowercase())) {
+            Some(substring) => Err(format!("contains '{}'", substring)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A class of characters, for `RequiredClasses`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    Symbol,
+}
+
+impl CharClass {
+    fn name(self) -> &'static str {
+        match self {
+            CharClass::Lower => "lower",
+            CharClass::Upper => "upper",
+            CharClass::Digit => "digit",
+            CharClass::Symbol => "symbol",
+        }
+    }
+
+    fn contains(self, ch: char) -> bool {
+        match self {
+            CharClass::Lower => ch.is_lowercase(),
+            CharClass::Upper => ch.is_uppercase(),
+            CharClass::Digit => ch.is_numeric(),
+            CharClass::Symbol => !ch.is_alphanumeric() && !ch.is_whitespace(),
+        }
+    }
+}
+
+/// At least one character of each class.
+pub struct RequiredClasses(pub Vec<CharClass>);
+
+impl PasswordPolicy for RequiredClasses {
+    fn name(&self) -> &str {
+        "classes"
+    }
+
+    fn check(&self, password: &Password) -> Result<(), String> {
+        let missing: Vec<&str> = self
+            .0
+            .iter()
+            .filter(|class| !password.pwd.chars().any(|ch| class.contains(ch)))
+            .map(|class| class.name())
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("no {} character", missing.join(" or ")))
+        }
+    }
+}
+
+/// Matches a regular expression.
+pub struct Pattern(pub Regex);
+
+impl PasswordPolicy for Pattern {
+    fn name(&self) -> &str {
+        "pattern"
+    }
+
+    fn check(&self, password: &Password) -> Result<(), String> {
+        if self.0.is_match(&password.pwd) {
+            Ok(())
+        } else {
+            Err(format!("does not match '{}'", self.0))
+        }
+    }
+}
+
+/// Policy from a spec like `count`, `position` (or `position=graphemes`),
+/// `min-length=12`, `forbid=password,1234`, `classes=lower,upper,digit,symbol`
+/// or `pattern=^[a-z]+$`.
+pub fn from_spec(spec: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+    let (name, value) = match spec.find('=') {
+        Some(i) => (&spec[..i], Some(&spec[(i + 1)..])),
+        None => (spec, None),
+    };
+    let required = || value.ok_or(format!("Policy {} needs a value, like {}=...", name, name));
+    Ok(match name {
+        "count" => Box::new(CountPolicy),
+        "position" => {
+            let unit = match value {
+                None | Some("chars") => Unit::Char,
+                Some("graphemes") => Unit::Grapheme,
+                Some(other) => return Err(format!("Unknown position unit: {}", other)),
+            };
+            Box::new(PositionPolicy { unit })
+        }
+        "min-length" => {
+            let value = required()?;
+            let len = value
+                .parse()
+                .map_err(|_| format!("Invalid length: {}", value))?;
+            Box::new(MinLength(len))
+        }
+        "forbid" => Box::new(Forbidden(
+            required()?.split(',').map(String::from).collect(),
+        )),
+        "classes" => {
+            let classes = required()?
+                .split(',')
+                .map(|class| match class {
+                    "lower" => Ok(CharClass::Lower),
+                    "upper" => Ok(CharClass::Upper),
+                    "digit" => Ok(CharClass::Digit),
+                    "symbol" => Ok(CharClass::Symbol),
+                    _ => Err(format!("Unknown character class: {}", class)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Box::new(RequiredClasses(classes))
+        }
+        "pattern" => {
+            let value = required()?;
+            let regex = Regex::new(value).map_err(|e| format!("Invalid pattern: {}", e))?;
+            Box::new(Pattern(regex))
+        }
+        _ => return Err(format!("Unknown policy: {}", name)),
+    })
+}
+
+/// A policy that a password breaks, and why.
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub policy: String,
+    pub reason: String,
+}
+
+/// Every policy that the password breaks. Empty if the password is valid.
+pub fn violations(policies: &[Box<dyn PasswordPolicy>], password: &Password) -> Vec<Violation> {
+    policies
+        .iter()
+        .filter_map(|policy| {
+            policy.check(password).err().map(|reason| Violation {
+                policy: String::from(policy.name()),
+                reason,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn check(spec: &str, line: &str) -> Result<(), String> {
+        from_spec(spec).unwrap().check(&parse(line).unwrap()[0])
+    }
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(check("count", "1-3 a: abcde"), Ok(()));
+        assert_eq!(check("count", "2-9 c: ccccccccc"), Ok(()));
+        assert_eq!(
+            check("count", "1-3 b: cdefg"),
+            Err(String::from("'b' occurs 0 times, expected 1 to 3"))
+        );
+        assert_eq!(check("position", "1-3 a: abcde"), Ok(()));
+        assert_eq!(
+            check("position", "2-9 c: ccccccccc"),
+            Err(String::from("'c' at both positions 2 and 9"))
+        );
+        assert_eq!(
+            check("position", "1-9 a: abcde"),
+            Err(String::from("position 9 is outside the 5 characters"))
+        );
+        // 'é' is one character, but two bytes.
+        assert_eq!(check("position", "2-3 b: ébc"), Ok(()));
+        // 'e' and a combining acute accent are two characters, but one grapheme.
+        let combined = "1-2 b: e\u{301}bc";
+        assert_eq!(
+            check("position", combined),
+            Err(String::from("'b' at neither position 1 nor 2"))
+        );
+        assert_eq!(check("position=graphemes", combined), Ok(()));
+        assert!(from_spec("position=bytes").is_err());
+        assert_eq!(check("min-length=5", "1-3 a: abcde"), Ok(()));
+        assert!(check("min-length=6", "1-3 a: abcde").is_err());
+        assert_eq!(
+            check("forbid=123,BCD", "1-3 a: abcde"),
+            Err(String::from("contains 'BCD'"))
+        );
+        assert_eq!(check("classes=lower,digit", "1-3 a: ab3"), Ok(()));
+        assert_eq!(
+            check("classes=lower,upper,symbol", "1-3 a: ab3"),
+            Err(String::from("no upper or symbol character"))
+        );
+        assert_eq!(check("pattern=^[a-e]+$", "1-3 a: abcde"), Ok(()));
+        assert!(check("pattern=^[a-e]+$", "1-3 a: abcdef").is_err());
+
+        assert!(from_spec("min-length").is_err());
+        assert!(from_spec("classes=lower,other").is_err());
+        assert!(from_spec("pattern=(").is_err());
+        assert!(from_spec("unknown").is_err());
+    }
+
+    #[test]
+    fn test_violations() {
+        let policies = vec![from_spec("count").unwrap(), from_spec("position").unwrap()];
+        let v = parse("1-3 a: abcde\n1-3 b: cdefg").unwrap();
+        assert!(violations(&policies, &v[0]).is_empty());
+        let found = violations(&policies, &v[1]);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[1].policy, "position");
+        assert_eq!(found[1].reason, "'b' at neither position 1 nor 3");
+
+        let v = parse("0-3 a: abc").unwrap();
+        let found = violations(&policies, &v[0]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].policy, "position");
+        assert_eq!(found[0].reason, "position 0 is outside the 3 characters");
+    }
+}
@@ -0,0 +1,146 @@
+//! Audit report of the lines that break password policies.
+
+use crate::policy::{self, PasswordPolicy};
+use crate::Password;
+use serde_json::json;
+use std::io::{self, Write};
+
+/// One broken policy of one line.
+#[derive(Debug)]
+pub struct Finding<'a> {
+    /// Line number, starting at 1.
+    pub line: usize,
+    pub password: &'a Password,
+    pub policy: String,
+    pub reason: String,
+}
+
+/// Output format of `write_report`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// One line per finding, for reading.
+    Text,
+    /// A header and one row per finding.
+    Csv,
+    /// One JSON object per line for each finding.
+    Json,
+}
+
+impl Format {
+    /// Format from its name: `text`, `csv` or `json`.
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Every policy broken by every password, in line order. Each password of `v`
+/// is on its own line, as returned by `parse`.
+pub fn audit<'a>(v: &'a [Password], policies: &[Box<dyn PasswordPolicy>]) -> Vec<Finding<'a>> {
+    let mut findings = Vec::new();
+    for (i, password) in v.iter().enumerate() {
+        for violation in policy::violations(policies, password) {
+            findings.push(Finding {
+                line: i + 1,
+                password,
+                policy: violation.policy,
+                reason: violation.reason,
+            });
+        }
+    }
+    findings
+}
+
+/// Write the findings to `out` in the given format.
+pub fn write_report(findings: &[Finding], format: Format, out: &mut dyn Write) -> io::Result<()> {
+    match format {
+        Format::Text => {
+            for finding in findings {
+                let p = finding.password;
+                writeln!(
+                    out,
+                    "Line {}: {}-{} {}: {}: {}: {}",
+                    finding.line, p.nr1, p.nr2, p.ch, p.pwd, finding.policy, finding.reason
+                )?;
+            }
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(["line", "nr1", "nr2", "ch", "password", "policy", "reason"])?;
+            for finding in findings {
+                let p = finding.password;
+                writer.write_record(&[
+                    finding.line.to_string(),
+                    p.nr1.to_string(),
+                    p.nr2.to_string(),
+                    p.ch.to_string(),
+                    p.pwd.clone(),
+                    finding.policy.clone(),
+                    finding.reason.clone(),
+                ])?;
+            }
+            writer.flush()?;
+        }
+        Format::Json => {
+            for finding in findings {
+                let p = finding.password;
+                let value = json!({
+                    "line": finding.line,
+                    "nr1": p.nr1,
+                    "nr2": p.nr2,
+                    "ch": p.ch,
+                    "password": p.pwd,
+                    "policy": finding.policy,
+                    "reason": finding.reason,
+                });
+                writeln!(out, "{}", value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::policy::from_spec;
+
+    fn report(format: Format) -> String {
+        let v = parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc").unwrap();
+        let policies = vec![from_spec("count").unwrap(), from_spec("position").unwrap()];
+        let findings = audit(&v, &policies);
+        let mut out = Vec::new();
+        write_report(&findings, format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            report(Format::Text),
+            "Line 2: 1-3 b: cdefg: count: 'b' occurs 0 times, expected 1 to 3
+Line 2: 1-3 b: cdefg: position: 'b' at neither position 1 nor 3
+Line 3: 2-9 c: ccccccccc: position: 'c' at both positions 2 and 9
+"
+        );
+        assert_eq!(
+            report(Format::Csv),
+            "line,nr1,nr2,ch,password,policy,reason
+2,1,3,b,cdefg,count,\"'b' occurs 0 times, expected 1 to 3\"
+2,1,3,b,cdefg,position,'b' at neither position 1 nor 3
+3,2,9,c,ccccccccc,position,'c' at both positions 2 and 9
+"
+        );
+        let json = report(Format::Json);
+        let first: serde_json::Value = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+        assert_eq!(first["line"], 2);
+        assert_eq!(first["ch"], "b");
+        assert_eq!(first["policy"], "count");
+        assert_eq!(json.lines().count(), 3);
+    }
+}
@@ -0,0 +1,198 @@
+//! Random jigsaw tiles cut from a random image with sea monsters.
+//!
+//! The tiles are cut from a grid of borders and image pixels, where
+//! neighbouring tiles share a border. Every border is unique, also when
+//! reversed, so that each tile only fits in one place and in one orientation.
+
+use crate::transform_point;
+use common::{Generated, Rng};
+use std::collections::HashSet;
+
+const TILE_WIDTH: usize = 10;
+const MONSTER: [&str; 3] = [
+    "..................#.",
+    "#....##....##....###",
+    ".#..#..#..#..#..#...",
+];
+
+// Pixels of the monster, as (x, y).
+fn monster_pixels() -> Vec<(usize, usize)> {
+    let mut pixels = Vec::new();
+    for (y, line) in MONSTER.iter().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            if ch == '#' {
+                pixels.push((x, y));
+            }
+        }
+    }
+    pixels
+}
+
+// Number of monsters in the square image, in any orientation.
+fn count_monsters(image: &[Vec<bool>]) -> usize {
+    let width = image.len();
+    let monster = monster_pixels();
+    let mut count = 0;
+    for flop in 0..8 {
+        let pixel = |x: usize, y: usize| {
+            let (x, y) = transform_point(width, flop, x, y);
+            image[y][x]
+        };
+        for y in 0..=(width - MONSTER.len()) {
+            for x in 0..=(width - MONSTER[0].len()) {
+                if monster.iter().all(|&(dx, dy)| pixel(x + dx, y + dy)) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+// Square image with some monsters, that are not in the outermost pixels and
+// do not overlap. Returns the image and the number of monsters.
+fn monster_image(rng: &mut Rng, width: usize) -> (Vec<Vec<bool>>, usize) {
+    let (monster_width, monster_height) = (MONSTER[0].len(), MONSTER.len());
+    loop {
+        let mut image: Vec<Vec<bool>> = (0..width)
+            .map(|_| (0..width).map(|_| rng.chance(35)).collect())
+            .collect();
+        let mut placed: Vec<(usize, usize)> = Vec::new();
+        for _ in 0..(width * width / 200) {
+            let x = 1 + rng.below(width - monster_width - 1);
+            let y = 1 + rng.below(width - monster_height - 1);
+            let overlaps = placed.iter().any(|&(other_x, other_y)| {
+                x < other_x + monster_width
+                    && other_x < x + monster_width
+                    && y < other_y + monster_height
+                    && other_y < y + monster_height
+            });
+            if !overlaps {
+                placed.push((x, y));
+            }
+        }
+        for &(x, y) in &placed {
+            for (dx, dy) in monster_pixels() {
+                image[y + dy][x + dx] = true;
+            }
+        }
+        if count_monsters(&image) == placed.len() {
+            return (image, placed.len());
+        }
+    }
+}
+
+// Grid of side `tiles * (TILE_WIDTH - 1) + 1`, where every `TILE_WIDTH - 1`
+// row and column is a border and the rest is the image.
+fn tile_grid(rng: &mut Rng, tiles: usize, image: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let step = TILE_WIDTH - 1;
+    let width = tiles * step + 1;
+    let mut grid = vec![vec![false; width]; width];
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = if x % step == 0 || y % step == 0 {
+                rng.chance(50)
+            } else {
+                image[y - 1 - y / step][x - 1 - x / step]
+            };
+        }
+    }
+
+    // Every border segment between two corners, as start and direction.
+    let mut segments: Vec<(usize, usize, bool)> = Vec::new();
+    for a in 0..=tiles {
+        for b in 0..tiles {
+            segments.push((b * step, a * step, true));
+            segments.push((a * step, b * step, false));
+        }
+    }
+    let pixels = |(x, y, horizontal): (usize, usize, bool), i: usize| match horizontal {
+        true => (x + i, y),
+        false => (x, y + i),
+    };
+    loop {
+        let mut seen: HashSet<Vec<bool>> = HashSet::new();
+        let mut redo = Vec::new();
+        for &segment in &segments {
+            let edge: Vec<bool> = (0..TILE_WIDTH)
+                .map(|i| {
+                    let (x, y) = pixels(segment, i);
+                    grid[y][x]
+                })
+                .collect();
+            let reversed: Vec<bool> = edge.iter().rev().copied().collect();
+            if edge == reversed || seen.contains(&edge) || seen.contains(&reversed) {
+                redo.push(segment);
+            } else {
+                seen.insert(edge);
+            }
+        }
+        if redo.is_empty() {
+            return grid;
+        }
+        // Keep the corners, which are shared with other segments.
+        for segment in redo {
+            for i in 1..(TILE_WIDTH - 1) {
+                let (x, y) = pixels(segment, i);
+                grid[y][x] = rng.chance(50);
+            }
+        }
+    }
+}
+
+/// Tiles for an image of `size` (3 to 12) times `size` tiles.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let tiles = size.clamp(3, 12);
+    let (image, nr_monsters) = monster_image(&mut rng, tiles * (TILE_WIDTH - 2));
+    let grid = tile_grid(&mut rng, tiles, &image);
+
+    let mut ids: Vec<usize> = (1000..10000).collect();
+    rng.shuffle(&mut ids);
+    let mut blocks = Vec::new();
+    for tile_y in 0..tiles {
+        for tile_x in 0..tiles {
+            let id = ids[tile_y * tiles + tile_x];
+            let flop = rng.below(8);
+            let mut block = format!("Tile {}:", id);
+            for y in 0..TILE_WIDTH {
+                block.push('\n');
+                for x in 0..TILE_WIDTH {
+                    let (x, y) = transform_point(TILE_WIDTH, flop, x, y);
+                    let pixel = grid[tile_y * (TILE_WIDTH - 1) + y][tile_x * (TILE_WIDTH - 1) + x];
+                    block.push(if pixel { '#' } else { '.' });
+                }
+            }
+            blocks.push(block);
+        }
+    }
+    rng.shuffle(&mut blocks);
+
+    let corners = [0, tiles - 1, tiles * (tiles - 1), tiles * tiles - 1];
+    let part1: i64 = corners.iter().map(|&i| ids[i] as i64).product();
+    let nr_set = image.iter().flatten().filter(|&&pixel| pixel).count();
+    let part2 = nr_set - nr_monsters * monster_pixels().len();
+    Generated {
+        input: blocks.join("\n\n"),
+        part1: part1.into(),
+        part2: part2.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day20;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..4 {
+            let generated = generate(seed, 3 + seed as usize);
+            let input = Day20::parse(&generated.input).unwrap();
+            assert_eq!(input.len(), (3 + seed as usize).pow(2));
+            assert_eq!(Day20::part1(&input), generated.part1);
+            assert_eq!(Day20::part2(&input), generated.part2);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Solutions to 2020: Advent of Code day 21
+//! By Peter Fornwall
+
+use common::{Answer, ParseError, Solution};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Ingredients and allergens of a food.
+pub type Food<'a> = (HashSet<&'a str>, HashSet<&'a str>);
+
+/// Number of times ingredients without allergens appear, and the dangerous
+/// ingredient list, sorted by allergen.
+pub fn parts(foods: &[Food]) -> (usize, String) {
+    let mut m: HashMap<&str, HashSet<&str>> = HashMap::new();
+    let mut all_allergens: HashSet<&str> = HashSet::new();
+    foods.iter().for_each(|x| {
+        x.0.iter().for_each(|i| {
+            m.insert(*i, HashSet::new());
+        });
+        all_allergens.extend(x.1.iter());
+    });
+    let all_ingredients: Vec<&str> = m.keys().copied().clone().collect();
+    // Map ingredient to set of allergens it cannot contain
+    for food in foods {
+        for one_ing in &all_ingredients {
+            if !food.0.contains(one_ing) {
+                m.get_mut(one_ing).unwrap().extend(food.1.clone());
+            }
+        }
+    }
+
+    let safe_ingredients: HashSet<_> = all_ingredients
+        .iter()
+        .filter(|&ingredient| m[ingredient].len() == all_allergens.len())
+        .collect();
+    let mut count_safe_used = 0;
+    for (ings, _) in foods {
+        for &clean in &safe_ingredients {
+            if ings.contains(clean) {
+                count_safe_used += 1;
+            }
+        }
+    }
+
+    let mut ing_to_aller_vec: Vec<(&str, &str)> = Vec::new();
+    loop {
+        let mut did_find = false;
+        for ing in &all_ingredients {
+            if m[ing].len() == (all_allergens.len() - 1) {
+                // Cannot contain all but one. Then has that one.
+                for test_aller in &all_allergens {
+                    if !m[ing].contains(test_aller) {
+                        // ing has test_aller
+                        did_find = true;
+                        ing_to_aller_vec.push((ing, test_aller));
+                        for mark in &all_ingredients {
+                            m.get_mut(mark).unwrap().insert(test_aller);
+                        }
+                    }
+                }
+            }
+        }
+        if !did_find {
+            break;
+        }
+    }
+    ing_to_aller_vec.sort_by(|a, b| a.1.cmp(b.1));
+    let sorted_ings: Vec<&str> = ing_to_aller_vec.iter().map(|x| x.0).collect();
+    let result_p2 = sorted_ings.join(",");
+
+    (count_safe_used, result_p2)
+}
+
+/// Parse lines like `mxmxvkd kfcds (contains dairy, fish)`.
+pub fn parse(content: &str) -> Result<Vec<Food<'_>>, ParseError> {
+    let mut foods: Vec<Food> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        let mut parts_it = line.split(" (contains ");
+        let ingredients_str = parts_it.next().unwrap_or(line);
+        let ingredients: HashSet<&str> = ingredients_str.split(' ').collect();
+        let allergens_str = parts_it
+            .next()
+            .ok_or_else(|| ParseError::after(content, ingredients_str, "expected ' (contains '"))?;
+        if !allergens_str.ends_with(')') {
+            return Err(ParseError::after(content, allergens_str, "expected ')'"));
+        }
+        let allergens: HashSet<&str> = allergens_str.trim_end_matches(')').split(", ").collect();
+        foods.push((ingredients, allergens));
+    }
+    Ok(foods)
+}
+
+/// Day 21 as a `Solution`, as used by the aoc runner.
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Vec<Food<'a>>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        parts(input).0.into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        parts(input).1.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+        let v = parse(input).unwrap();
+        let (result_p1, result_p2) = parts(&v);
+        assert_eq!(result_p1, 5);
+        assert_eq!(result_p2, "mxmxvkd,sqjhc,fvjkl");
+    }
+}
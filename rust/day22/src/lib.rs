@@ -0,0 +1,152 @@
+//! Solutions to 2020: Advent of Code day 22
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+fn get_score(cards: &[VecDeque<usize>], p1_wins: bool) -> usize {
+    let winner_cards = if p1_wins { &cards[0] } else { &cards[1] };
+    let mut score = 0;
+    for i in 0..winner_cards.len() {
+        score += winner_cards[winner_cards.len() - 1 - i] * (i + 1);
+    }
+    score
+}
+
+fn rec_play(v: &[VecDeque<usize>], nr1: usize, nr2: usize, do_recurse: bool) -> (usize, bool) {
+    let mut cards: Vec<VecDeque<usize>> = vec![
+        v[0].iter().take(nr1).copied().collect(),
+        v[1].iter().take(nr2).copied().collect(),
+    ];
+
+    let mut seen = HashSet::new();
+    while !cards[0].is_empty() && !cards[1].is_empty() {
+        if seen.contains(&cards) {
+            return (get_score(&cards, true), true);
+        }
+        seen.insert(cards.clone());
+
+        let c1 = cards[0].pop_front().unwrap();
+        let c2 = cards[1].pop_front().unwrap();
+        let p1_wins = if do_recurse && c1 <= cards[0].len() && c2 <= cards[1].len() {
+            rec_play(&cards, c1, c2, do_recurse).1
+        } else {
+            c1 > c2
+        };
+        if p1_wins {
+            cards[0].push_back(c1);
+            cards[0].push_back(c2);
+        } else {
+            cards[1].push_back(c2);
+            cards[1].push_back(c1);
+        };
+    }
+
+    let p1_wins = cards[1].is_empty();
+    (get_score(&cards, p1_wins), p1_wins)
+}
+
+/// Score of the winner of Combat.
+pub fn part1(v: &[VecDeque<usize>]) -> usize {
+    let (score, _) = rec_play(v, v[0].len(), v[1].len(), false);
+    score
+}
+
+/// Score of the winner of Recursive Combat.
+pub fn part2(v: &[VecDeque<usize>]) -> usize {
+    let (score, _) = rec_play(v, v[0].len(), v[1].len(), true);
+    score
+}
+
+/// Parse the decks of the two players, from top to bottom.
+pub fn parse(content: &str) -> Result<Vec<VecDeque<usize>>, ParseError> {
+    let players: Vec<&str> = content.trim().split("\n\n").collect();
+    if players.len() != 2 {
+        return Err(ParseError::after(
+            content,
+            content.trim_end(),
+            format!("expected 2 players, found {}", players.len()),
+        ));
+    }
+    players
+        .iter()
+        .enumerate()
+        .map(|(index, x)| {
+            let mut lines = x.trim().split('\n').map(|x| x.trim());
+            let header = lines.next().unwrap_or(x);
+            if header != format!("Player {}:", index + 1) {
+                return Err(ParseError::at(
+                    content,
+                    header,
+                    format!("expected 'Player {}:'", index + 1),
+                ));
+            }
+            lines.map(|x| parse_nr(content, x)).collect()
+        })
+        .collect()
+}
+
+/// Day 22 as a `Solution`, as used by the aoc runner.
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = Vec<VecDeque<usize>>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 306);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 291);
+    }
+}
@@ -0,0 +1,147 @@
+//! Solutions to 2020: Advent of Code day 23
+//! By Peter Fornwall
+
+use common::{Answer, ParseError, Solution};
+
+/// Play `nr_rounds` moves with the cups in `input` followed by the labels up
+/// to `size`. With only the cups in `input`, the labels after cup 1 are returned, otherwise the
+/// product of the two labels after cup 1.
+pub fn part(input: &str, size: usize, nr_rounds: usize) -> String {
+    let circle_org: Vec<u32> = input.chars().map(|x| x as u32 - '0' as u32).collect();
+
+    let mut circle: Vec<u32> = vec![0; size + 1];
+    for index in 0..size {
+        let cur_value = *circle_org.get(index).unwrap_or(&(index as u32 + 1));
+        let mut next_value = *circle_org.get(index + 1).unwrap_or(&(index as u32 + 2));
+        if next_value > size as u32 {
+            next_value = circle_org[0];
+        }
+        circle[cur_value as usize] = next_value;
+    }
+
+    let mut cur_value = circle_org[0];
+    for _ in 1..=nr_rounds {
+        let val1 = circle[cur_value as usize];
+        let val2 = circle[val1 as usize];
+        let val3 = circle[val2 as usize];
+
+        let mut destination_cup = cur_value;
+        loop {
+            destination_cup -= 1;
+            if destination_cup == 0 {
+                destination_cup = size as u32;
+            }
+            if destination_cup != val1 && destination_cup != val2 && destination_cup != val3 {
+                break;
+            }
+        }
+        // Remove from current list
+        circle[cur_value as usize] = circle[val3 as usize];
+        // Insert after destination cup
+        circle[val3 as usize] = circle[destination_cup as usize];
+        circle[destination_cup as usize] = val1;
+
+        cur_value = circle[cur_value as usize];
+    }
+
+    let mut s: String;
+    if size == input.len() {
+        s = String::new();
+        let mut current = 1;
+        for _ in 1..size {
+            current = circle[current as usize];
+            s += &current.to_string();
+        }
+    } else {
+        let val1 = circle[1];
+        let val2 = circle[val1 as usize];
+        let product = val1 as u64 * val2 as u64;
+        s = product.to_string();
+    }
+    s
+}
+
+/// The cups must be labeled 1 to the number of cups, each label used once.
+/// A move picks up three cups and needs a fourth as the destination, so there
+/// must be at least four cups.
+pub fn parse(content: &str) -> Result<&str, ParseError> {
+    let input = content.trim();
+    let mut seen = vec![false; input.len() + 1];
+    for (i, ch) in input.char_indices() {
+        match ch.to_digit(10) {
+            Some(nr) if nr >= 1 && (nr as usize) < seen.len() && !seen[nr as usize] => {
+                seen[nr as usize] = true;
+            }
+            _ => {
+                return Err(ParseError::at(
+                    content,
+                    &input[i..],
+                    format!("unexpected cup label '{}'", ch),
+                ))
+            }
+        }
+    }
+    if input.len() < 4 {
+        return Err(ParseError::after(
+            content,
+            input,
+            format!("expected at least 4 cups, found {}", input.len()),
+        ));
+    }
+    Ok(input)
+}
+
+/// Day 23 as a `Solution`, as used by the aoc runner.
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = &'a str;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part(input, input.len(), 100).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part(input, 1_000_000, 10_000_000).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "389125467";
+        let result = part(input, 9, 10);
+        assert_eq!(result, "92658374");
+
+        let result = part(input, 9, 100);
+        assert_eq!(result, "67384529");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("389125467\n"), Ok("389125467"));
+        assert_eq!(parse("4132"), Ok("4132"));
+        for input in ["", "1", "12", "123"] {
+            let e = parse(input).unwrap_err();
+            assert_eq!(
+                e.message,
+                format!("expected at least 4 cups, found {}", input.len())
+            );
+        }
+        assert!(parse("1224").is_err());
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "389125467";
+        let result = part(input, 1_000_000, 10_000_000);
+        assert_eq!(result, "149245887792");
+    }
+}
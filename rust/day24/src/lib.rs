@@ -0,0 +1,152 @@
+//! Solutions to 2020: Advent of Code day 24
+//! By Peter Fornwall
+
+use common::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+type Coord = i8;
+// map direction to coordinate delta
+static DIRS: [(&str, (Coord, Coord)); 6] = [
+    ("se", (0, 1)),
+    ("sw", (-1, 1)),
+    ("nw", (0, -1)),
+    ("ne", (1, -1)),
+    ("e", (1, 0)),
+    ("w", (-1, 0)),
+];
+
+fn get_black_tiles(init_flips: &[&str]) -> HashSet<(Coord, Coord)> {
+    let mut black_tiles: HashSet<(Coord, Coord)> = HashSet::new();
+    for line in init_flips {
+        let mut i = 0;
+        let mut pos = (0, 0);
+        while i < line.len() {
+            for dir in DIRS.iter() {
+                if line[i..].starts_with(dir.0) {
+                    pos = (pos.0 + dir.1 .0, pos.1 + dir.1 .1);
+                    i += dir.0.len();
+                }
+            }
+        }
+        if black_tiles.contains(&pos) {
+            black_tiles.remove(&pos);
+        } else {
+            black_tiles.insert(pos);
+        }
+    }
+    black_tiles
+}
+
+/// Number of black tiles after flipping the tiles in `init_flips`.
+pub fn part1(init_flips: &[&str]) -> usize {
+    get_black_tiles(init_flips).len()
+}
+
+fn count_black_neighbors(black_tiles: &HashSet<(Coord, Coord)>, check: &(Coord, Coord)) -> usize {
+    DIRS.iter()
+        .filter(|(_, dir)| black_tiles.contains(&(check.0 + dir.0, check.1 + dir.1)))
+        .count()
+}
+
+/// Number of black tiles after 100 days.
+pub fn part2(init_flips: &[&str]) -> usize {
+    let mut black_tiles = get_black_tiles(init_flips);
+    for _ in 0..100 {
+        let mut new_black_tiles = HashSet::new();
+        for check in &black_tiles {
+            let nr_black_neighbors = count_black_neighbors(&black_tiles, check);
+
+            // Check if we should keep this tile black
+            if nr_black_neighbors == 1 || nr_black_neighbors == 2 {
+                new_black_tiles.insert(*check);
+            }
+
+            // For all white neighbors, check if we should flip it to black
+            for (_, dir) in DIRS.iter() {
+                let white_check = (check.0 + dir.0, check.1 + dir.1);
+                if !black_tiles.contains(&white_check)
+                    && count_black_neighbors(&black_tiles, &white_check) == 2
+                {
+                    new_black_tiles.insert(white_check);
+                }
+            }
+        }
+        black_tiles = new_black_tiles;
+    }
+    black_tiles.len()
+}
+
+// Check that a line only consists of the directions in DIRS.
+fn check_directions<'a>(content: &str, line: &'a str) -> Result<&'a str, ParseError> {
+    let mut i = 0;
+    while i < line.len() {
+        match DIRS.iter().find(|dir| line[i..].starts_with(dir.0)) {
+            Some(dir) => i += dir.0.len(),
+            None => return Err(ParseError::at(content, &line[i..], "unknown direction")),
+        }
+    }
+    Ok(line)
+}
+
+/// Parse one tile per line, as steps like `esenee`.
+pub fn parse(content: &str) -> Result<Vec<&str>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|x| check_directions(content, x.trim()))
+        .collect()
+}
+
+/// Day 24 as a `Solution`, as used by the aoc runner.
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts() {
+        let input = "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+        let init_flips = parse(input).unwrap();
+        let result = part1(&init_flips);
+        assert_eq!(result, 10);
+
+        let result = part2(&init_flips);
+        assert_eq!(result, 2208);
+    }
+}
@@ -0,0 +1,300 @@
+//! Forest map packed as one bit per square, for maps with millions of rows.
+
+use crate::Slope;
+use common::ParseError;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// Error of `BitGrid::read`.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{}", e),
+            ReadError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+/// Forest map where each row is packed in 64-bit words, and a set bit is a
+/// tree. Like the map of `parse`, it repeats to the right.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Map of `width` times `height` squares without trees.
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        let words_per_row = width.div_ceil(64);
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Map from rows of squares, as returned by `parse`.
+    pub fn from_rows(rows: &[Vec<bool>]) -> BitGrid {
+        let mut grid = BitGrid::new(rows.first().map_or(0, |row| row.len()), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &tree) in row.iter().enumerate() {
+                grid.set(x, y, tree);
+            }
+        }
+        grid
+    }
+
+    /// Parse the map, where `#` is a tree, without first building rows of
+    /// `bool`.
+    pub fn parse(content: &str) -> Result<BitGrid, ParseError> {
+        BitGrid::read(content.as_bytes()).map_err(|e| match e {
+            ReadError::Parse(e) => e,
+            // Reading from a string only fails on invalid UTF-8.
+            ReadError::Io(e) => ParseError::new(1, 1, e.to_string()),
+        })
+    }
+
+    /// Read the map one row at a time, so that only the packed map is kept in
+    /// memory. Blank lines are allowed before and after the map.
+    pub fn read(mut reader: impl BufRead) -> Result<BitGrid, ReadError> {
+        let mut grid = BitGrid::new(0, 0);
+        let mut buf = String::new();
+        let mut line_nr = 0;
+        let mut blank_line = None;
+        loop {
+            buf.clear();
+            if reader.read_line(&mut buf)? == 0 {
+                break;
+            }
+            line_nr += 1;
+            let line = buf.trim();
+            if line.is_empty() {
+                if grid.height > 0 && blank_line.is_none() {
+                    blank_line = Some(line_nr);
+                }
+                continue;
+            }
+            if let Some(blank_line) = blank_line {
+                return Err(ReadError::Parse(ParseError::new(
+                    blank_line,
+                    1,
+                    "empty row",
+                )));
+            }
+            grid.push_row(&buf, line)
+                .map_err(|e| ReadError::Parse(ParseError::new(line_nr, e.column, e.message)))?;
+        }
+        Ok(grid)
+    }
+
+    // Add `line`, a slice of `text`, as the bottom row. Errors are located in
+    // `text`.
+    fn push_row(&mut self, text: &str, line: &str) -> Result<(), ParseError> {
+        if self.height == 0 {
+            *self = BitGrid::new(line.chars().count(), 0);
+        }
+        let y = self.height;
+        self.height += 1;
+        self.words.resize(self.words_per_row * self.height, 0);
+        let mut nr_columns = 0;
+        for (i, ch) in line.char_indices() {
+            let tree = match ch {
+                '#' => true,
+                '.' => false,
+                _ => {
+                    return Err(ParseError::at(
+                        text,
+                        &line[i..],
+                        format!("unexpected character '{}'", ch),
+                    ))
+                }
+            };
+            if nr_columns < self.width {
+                self.set(nr_columns, y, tree);
+            }
+            nr_columns += 1;
+        }
+        if nr_columns != self.width {
+            return Err(ParseError::at(
+                text,
+                line,
+                format!("expected {} columns, found {}", self.width, nr_columns),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// True if there is a tree at (`x`, `y`), with `x` below the width.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let word = self.words[y * self.words_per_row + x / 64];
+        (word >> (x % 64)) & 1 == 1
+    }
+
+    /// Put or remove a tree at (`x`, `y`), with `x` below the width.
+    pub fn set(&mut self, x: usize, y: usize, tree: bool) {
+        let word = &mut self.words[y * self.words_per_row + x / 64];
+        if tree {
+            *word |= 1 << (x % 64);
+        } else {
+            *word &= !(1 << (x % 64));
+        }
+    }
+
+    /// Trees hit on each of the slopes, like `find`, but for all slopes in
+    /// one pass over the rows. Errors if a slope does not go down.
+    pub fn trees_per_slope(&self, slopes: &[Slope]) -> Result<Vec<i64>, String> {
+        match slopes.iter().find(|&&(_, down)| down == 0) {
+            Some((right, down)) => Err(format!("slope {},{} does not go down", right, down)),
+            None => Ok(self.count_trees(slopes)),
+        }
+    }
+
+    // Slopes that go the same number of rows down visit the same rows, so
+    // they are grouped, and each group checks which row it visits next
+    // instead of every slope. The words of a row are looked up once, for all
+    // slopes that visit it.
+    fn count_trees(&self, slopes: &[Slope]) -> Vec<i64> {
+        let mut trees = vec![0; slopes.len()];
+        if self.width == 0 {
+            return trees;
+        }
+        let mut by_down: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, &(_, down)) in slopes.iter().enumerate() {
+            by_down.entry(down).or_default().push(i);
+        }
+        // (rows down, next row visited, slopes)
+        let mut groups: Vec<(usize, usize, Vec<usize>)> = by_down
+            .into_iter()
+            .map(|(down, group)| (down, down, group))
+            .collect();
+        let steps: Vec<usize> = slopes
+            .iter()
+            .map(|&(right, _)| right % self.width)
+            .collect();
+        let mut columns = vec![0; slopes.len()];
+        let rows = self.words.chunks_exact(self.words_per_row);
+        for (y, row) in rows.enumerate().skip(1) {
+            for (down, next_y, group) in &mut groups {
+                if *next_y != y {
+                    continue;
+                }
+                *next_y += *down;
+                for &i in group.iter() {
+                    columns[i] += steps[i];
+                    if columns[i] >= self.width {
+                        columns[i] -= self.width;
+                    }
+                    let x = columns[i];
+                    trees[i] += ((row[x / 64] >> (x % 64)) & 1) as i64;
+                }
+            }
+        }
+        trees
+    }
+
+    /// Like `best_slope`, with all slopes counted in one pass.
+    pub fn best_slope(&self, max_right: usize, max_down: usize) -> Option<(Slope, i64)> {
+        let slopes: Vec<Slope> = (1..=max_down)
+            .flat_map(|down| (0..=max_right).map(move |right| (right, down)))
+            .collect();
+        let trees = self.count_trees(&slopes);
+        let mut best: Option<(Slope, i64)> = None;
+        for (&slope, &trees) in slopes.iter().zip(&trees) {
+            if best.is_none_or(|(_, fewest)| trees < fewest) {
+                best = Some((slope, trees));
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find, parse};
+    use common::Rng;
+
+    #[test]
+    fn test_bitgrid() {
+        let mut rng = Rng::new(3);
+        let mut content = String::new();
+        for _ in 0..500 {
+            for _ in 0..100 {
+                content.push(if rng.chance(30) { '#' } else { '.' });
+            }
+            content.push('\n');
+        }
+        let rows = parse(&content).unwrap();
+        let grid = BitGrid::parse(&content).unwrap();
+        assert_eq!(grid, BitGrid::from_rows(&rows));
+        assert_eq!((grid.width(), grid.height()), (100, 500));
+
+        let slopes: Vec<Slope> = (1..=4)
+            .flat_map(|down| (0..=130).map(move |right| (right, down)))
+            .collect();
+        let expected: Vec<i64> = slopes
+            .iter()
+            .map(|&(right, down)| find(&rows, right, down))
+            .collect();
+        assert_eq!(grid.trees_per_slope(&slopes), Ok(expected.clone()));
+        let (best, trees) = grid.best_slope(130, 4).unwrap();
+        assert_eq!(trees, *expected.iter().min().unwrap());
+        assert_eq!(find(&rows, best.0, best.1), trees);
+
+        let e = grid.trees_per_slope(&[(1, 1), (3, 0)]).unwrap_err();
+        assert_eq!(e, "slope 3,0 does not go down");
+    }
+
+    #[test]
+    fn test_read() {
+        let content = "\r\n..#\r\n#..\r\n.#.\r\n\r\n";
+        let grid = BitGrid::read(content.as_bytes()).unwrap();
+        assert_eq!(grid, BitGrid::parse("..#\n#..\n.#.").unwrap());
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert!(grid.get(2, 0) && grid.get(0, 1) && !grid.get(0, 2));
+
+        let e = BitGrid::read("..#\n\n#..\n".as_bytes()).unwrap_err();
+        match e {
+            ReadError::Parse(e) => assert_eq!((e.line, e.message.as_str()), (2, "empty row")),
+            ReadError::Io(e) => panic!("{}", e),
+        }
+        let e = BitGrid::read(&[b'.', b'#', 0xff][..]).unwrap_err();
+        assert!(matches!(e, ReadError::Io(_)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = BitGrid::parse("..#\n.#\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        let e = BitGrid::parse("..#\n.x.\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+}
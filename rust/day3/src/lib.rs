@@ -0,0 +1,239 @@
+//! Solutions to 2020: Advent of Code day 3
+//! By Peter Fornwall
+
+use common::{parse_grid, parse_nr, Answer, ParseError, Solution};
+
+pub mod bitgrid;
+
+pub use bitgrid::BitGrid;
+
+/// Number of trees hit going `delta_x` right and `delta_y` down each step,
+/// from the top left until the bottom. The map repeats to the right.
+pub fn find(slope_map: &[Vec<bool>], delta_x: usize, delta_y: usize) -> i64 {
+    let mut x = 0;
+    let mut y = 0;
+    let height = slope_map.len();
+    let width = slope_map[0].len();
+    let mut trees = 0;
+    while y + delta_y < height {
+        x += delta_x;
+        x %= width;
+        y += delta_y;
+        if slope_map[y][x] {
+            trees += 1;
+        }
+    }
+    trees
+}
+
+/// A slope as steps right and down.
+pub type Slope = (usize, usize);
+
+/// The slopes of part 2.
+pub const PART2_SLOPES: [Slope; 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// Trees hit on each of the slopes.
+pub fn trees_per_slope(slope_map: &[Vec<bool>], slopes: &[Slope]) -> Vec<i64> {
+    slopes
+        .iter()
+        .map(|&(delta_x, delta_y)| find(slope_map, delta_x, delta_y))
+        .collect()
+}
+
+/// The slope with the fewest trees of all slopes going 0 to `max_right`
+/// right and 1 to `max_down` down, and its number of trees. On a tie, the
+/// slope with the least down, and then the least right, is chosen.
+pub fn best_slope(
+    slope_map: &[Vec<bool>],
+    max_right: usize,
+    max_down: usize,
+) -> Option<(Slope, i64)> {
+    BitGrid::from_rows(slope_map).best_slope(max_right, max_down)
+}
+
+/// Parse slopes like `3,1`, separated by whitespace or on separate lines.
+pub fn parse_slopes(content: &str) -> Result<Vec<Slope>, ParseError> {
+    content
+        .split_whitespace()
+        .map(|slope| {
+            let mut it = slope.split(',');
+            let right = it.next().unwrap_or(slope);
+            let down = it
+                .next()
+                .ok_or_else(|| ParseError::after(content, right, "expected ',' in slope"))?;
+            if let Some(extra) = it.next() {
+                return Err(ParseError::at(
+                    content,
+                    extra,
+                    "expected only right and down",
+                ));
+            }
+            let down: usize = parse_nr(content, down)?;
+            if down == 0 {
+                return Err(ParseError::at(content, slope, "down must be at least 1"));
+            }
+            Ok((parse_nr(content, right)?, down))
+        })
+        .collect()
+}
+
+/// Options for `render`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RenderOptions {
+    /// Repeat the map to the right as many times as the path needs, instead
+    /// of wrapping the path around a single copy.
+    pub tile: bool,
+    /// Color hits red and misses green with ANSI escape codes.
+    pub color: bool,
+}
+
+/// The map with the path of a slope drawn on it, as `O` where the path is
+/// open and `X` where it hits a tree, one line per row. Errors if the slope
+/// does not go down.
+pub fn render(
+    slope_map: &[Vec<bool>],
+    slope: Slope,
+    options: RenderOptions,
+) -> Result<String, String> {
+    let (delta_x, delta_y) = slope;
+    if delta_y == 0 {
+        return Err(format!("slope {},{} does not go down", delta_x, delta_y));
+    }
+    let height = slope_map.len();
+    let width = slope_map[0].len();
+    let nr_steps = (height - 1) / delta_y;
+    let nr_copies = if options.tile {
+        nr_steps * delta_x / width + 1
+    } else {
+        1
+    };
+    let mut result = String::new();
+    for (y, row) in slope_map.iter().enumerate() {
+        let path_x = match y % delta_y {
+            0 if y > 0 => Some(y / delta_y * delta_x % (width * nr_copies)),
+            _ => None,
+        };
+        for x in 0..(width * nr_copies) {
+            let tree = row[x % width];
+            let cell = match (path_x == Some(x), tree, options.color) {
+                (false, true, _) => "#",
+                (false, false, _) => ".",
+                (true, true, false) => "X",
+                (true, false, false) => "O",
+                (true, true, true) => "\x1b[31mX\x1b[0m",
+                (true, false, true) => "\x1b[32mO\x1b[0m",
+            };
+            result += cell;
+        }
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Trees hit on slope right 3, down 1.
+pub fn part1(slope_map: &[Vec<bool>]) -> i64 {
+    find(slope_map, 3, 1)
+}
+
+/// Product of the trees hit on the five slopes of part 2.
+pub fn part2(trees: &[Vec<bool>]) -> i64 {
+    trees_per_slope(trees, &PART2_SLOPES).iter().product()
+}
+
+/// Parse the map, where `#` is a tree.
+pub fn parse(content: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    parse_grid(content, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+/// Day 3 as a `Solution`, as used by the aoc runner.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<Vec<bool>>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_slopes() {
+        let v = parse(EXAMPLE).unwrap();
+        assert_eq!(trees_per_slope(&v, &PART2_SLOPES), vec![2, 7, 3, 4, 2]);
+        assert_eq!(part2(&v), 336);
+        assert_eq!(best_slope(&v, 7, 2), Some(((5, 2), 0)));
+        assert_eq!(best_slope(&v, 3, 1), Some(((2, 1), 1)));
+        assert_eq!(best_slope(&v, 3, 0), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let v = parse(EXAMPLE).unwrap();
+        let tiled = RenderOptions {
+            tile: true,
+            ..RenderOptions::default()
+        };
+        let rendered = render(&v, (3, 1), tiled).unwrap();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "..##.......".repeat(3));
+        assert_eq!(lines[1], format!("#..O#...#..{}", "#...#...#..".repeat(2)));
+        assert_eq!(&lines[10][30..], "X.#");
+        assert_eq!(rendered.matches('X').count(), 7);
+        assert_eq!(rendered.matches('O').count(), 3);
+
+        let wrapped = render(&v, (3, 1), RenderOptions::default()).unwrap();
+        assert_eq!(wrapped.lines().nth(4), Some(".X...##..#."));
+        assert_eq!(wrapped.matches('X').count(), 7);
+        let colored = RenderOptions {
+            color: true,
+            ..RenderOptions::default()
+        };
+        assert!(render(&v, (1, 2), colored)
+            .unwrap()
+            .contains("\x1b[31mX\x1b[0m"));
+        assert_eq!(
+            render(&v, (3, 0), tiled),
+            Err(String::from("slope 3,0 does not go down"))
+        );
+    }
+
+    #[test]
+    fn test_parse_slopes() {
+        assert_eq!(
+            parse_slopes("1,1 3,1\n1,2\n"),
+            Ok(vec![(1, 1), (3, 1), (1, 2)])
+        );
+        let e = parse_slopes("1,1\n3,0").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        let e = parse_slopes("1,1 3").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 6));
+    }
+}
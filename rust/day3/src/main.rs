@@ -1,52 +1,103 @@
 //! Solutions to 2020: Advent of Code day 3
 //! By Peter Fornwall
+//!
+//! Usage: day3 [INPUT] [--slopes LIST | --slopes-file PATH] [--best RIGHT,DOWN]
+//!        day3 [INPUT] --render RIGHT,DOWN [--tile] [--color]
+//!
+//! --slopes prints the trees hit on each slope of LIST, like "1,1 3,1 1,2",
+//! and --slopes-file reads the list from a file. --best searches every slope
+//! up to RIGHT right and DOWN down for the one with the fewest trees.
+//!
+//! --render draws the path of a slope on the map, as O where it is open and X
+//! where it hits a tree. --tile repeats the map to the right as far as the
+//! path goes, and --color colors the path.
 
+use std::env;
 use std::fs;
+use std::process;
 
-pub fn find(slope_map: &Vec<Vec<bool>>, delta_x: usize, delta_y: usize) -> i64 {
-    let mut x = 0;
-    let mut y = 0;
-    let height = slope_map.len();
-    let width = slope_map[0].len();
-    let mut trees = 0;
-    while y + delta_y < height {
-        x += delta_x;
-        x %= width;
-        y += delta_y;
-        if slope_map[y][x] {
-            trees += 1;
-        }
-    }
-    return trees;
-}
-
-pub fn part1(slope_map: &Vec<Vec<bool>>) -> i64 {
-    let trees: i64 = find(slope_map, 3, 1);
-    println!("Part1: {}", trees);
-    return trees;
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: day3 [INPUT] [--slopes LIST | --slopes-file PATH] [--best RIGHT,DOWN]");
+    eprintln!("       day3 [INPUT] --render RIGHT,DOWN [--tile] [--color]");
+    process::exit(2);
 }
 
-pub fn part2(trees: &Vec<Vec<bool>>) -> i64 {
-    let result: i64 = find(trees, 1, 1)
-        * find(trees, 3, 1)
-        * find(trees, 5, 1)
-        * find(trees, 7, 1)
-        * find(trees, 1, 2);
-    println!("Part2: {}", result);
-    return result;
+fn parse_slopes_or_exit(content: &str, source: &str) -> Vec<day3::Slope> {
+    day3::parse_slopes(content).unwrap_or_else(|e| exit_with_usage(&format!("{}: {}", source, e)))
 }
 
 fn main() {
-    let content = fs::read_to_string("../../inputs/day3_input.txt").expect("Cannot open file!");
-    let mut v = Vec::<Vec<bool>>::new();
-    for line in content.lines() {
-        let vec_line: Vec<bool> = line
-            .chars()
-            .map(|c| if c == '#' { true } else { false })
-            .collect();
-        v.push(vec_line);
+    let mut input = None;
+    let mut slopes = None;
+    let mut best = None;
+    let mut render = None;
+    let mut options = day3::RenderOptions::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| exit_with_usage(&format!("Missing value for {}", arg)))
+        };
+        match arg.as_str() {
+            "--slopes" => slopes = Some(parse_slopes_or_exit(&value(), "--slopes")),
+            "--slopes-file" => {
+                let path = value();
+                let content = fs::read_to_string(&path).unwrap_or_else(|e| {
+                    eprintln!("Cannot open {}: {}", path, e);
+                    process::exit(1);
+                });
+                slopes = Some(parse_slopes_or_exit(&content, &path));
+            }
+            "--best" => match parse_slopes_or_exit(&value(), "--best")[..] {
+                [bounds] => best = Some(bounds),
+                _ => exit_with_usage("--best takes one RIGHT,DOWN"),
+            },
+            "--render" => match parse_slopes_or_exit(&value(), "--render")[..] {
+                [slope] => render = Some(slope),
+                _ => exit_with_usage("--render takes one RIGHT,DOWN"),
+            },
+            "--tile" => options.tile = true,
+            "--color" => options.color = true,
+            _ => input = Some(arg),
+        }
+    }
+    if render.is_some() && (slopes.is_some() || best.is_some()) {
+        exit_with_usage("--render can not be combined with --slopes or --best");
     }
+    let source = common::InputSource::from_arg(input.as_deref(), 3);
 
-    let _result_p1 = part1(&v);
-    let _result_p2 = part2(&v);
+    if slopes.is_none() && best.is_none() {
+        let content = common::read_or_exit(&source);
+        let v = common::unwrap_or_exit(day3::parse(&content), &source);
+        if let Some(slope) = render {
+            let rendered = day3::render(&v, slope, options).unwrap_or_else(|e| exit_with_usage(&e));
+            print!("{}", rendered);
+            return;
+        }
+        let result_p1 = day3::part1(&v);
+        println!("Part1: {}", result_p1);
+        let result_p2 = day3::part2(&v);
+        println!("Part2: {}", result_p2);
+        return;
+    }
+    // The packed map, read one row at a time, takes much less memory, and
+    // counts all slopes at once.
+    let grid = day3::BitGrid::read(common::open_or_exit(&source)).unwrap_or_else(|e| {
+        eprintln!("{}: {}", source, e);
+        process::exit(1);
+    });
+    if let Some(slopes) = slopes {
+        let trees = grid
+            .trees_per_slope(&slopes)
+            .unwrap_or_else(|e| exit_with_usage(&e));
+        for ((right, down), trees) in slopes.iter().zip(trees) {
+            println!("Right {}, down {}: {} trees", right, down, trees);
+        }
+    }
+    if let Some((max_right, max_down)) = best {
+        if let Some(((right, down), trees)) = grid.best_slope(max_right, max_down) {
+            println!("Best: right {}, down {}: {} trees", right, down, trees);
+        }
+    }
 }
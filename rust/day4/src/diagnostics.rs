@@ -0,0 +1,233 @@
+//! Why passports are rejected: every broken field of each record, and a
+//! summary of the reasons across a batch.
+
+use crate::schema::{Failure, FieldError, Rule, Schema};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Kind of passport error, by the rule of the field and how it breaks it, so
+/// that it means the same with any schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorKind {
+    MissingField,
+    /// A field with a range, or with units, is not a number.
+    NotANumber,
+    /// A number is not in the range of the field.
+    OutOfRange,
+    /// A field with units does not end with any of them.
+    NoUnit,
+    /// A number is not in the range of its unit.
+    UnitOutOfRange,
+    /// A field does not match its pattern.
+    NoMatch,
+    /// A field is not one of its values.
+    UnknownValue,
+}
+
+impl ErrorKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::MissingField => "missing field",
+            ErrorKind::NotANumber => "not a number",
+            ErrorKind::OutOfRange => "out of range",
+            ErrorKind::NoUnit => "no unit",
+            ErrorKind::UnitOutOfRange => "out of range for its unit",
+            ErrorKind::NoMatch => "no pattern match",
+            ErrorKind::UnknownValue => "unknown value",
+        }
+    }
+
+    fn of(e: &FieldError, rule: &Rule) -> ErrorKind {
+        if e.failure == Failure::Missing {
+            return ErrorKind::MissingField;
+        }
+        match rule {
+            // Any value follows the rule, so the field can only be missing.
+            Rule::Any => ErrorKind::MissingField,
+            Rule::Range { .. } | Rule::Units(_) if e.failure == Failure::NotANumber => {
+                ErrorKind::NotANumber
+            }
+            Rule::Range { .. } => ErrorKind::OutOfRange,
+            Rule::Units(_) if e.failure == Failure::NoUnit => ErrorKind::NoUnit,
+            Rule::Units(_) => ErrorKind::UnitOutOfRange,
+            Rule::Pattern(_) => ErrorKind::NoMatch,
+            Rule::OneOf(_) => ErrorKind::UnknownValue,
+        }
+    }
+}
+
+/// One error of a passport record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassportError<'a> {
+    pub kind: ErrorKind,
+    pub error: FieldError<'a>,
+}
+
+/// Every error of the record, with each field checked on its own, instead of
+/// only the first one as in `Passport::parse`. Empty if the record is valid.
+pub fn diagnose<'a>(record: &'a str, schema: &Schema) -> Vec<PassportError<'a>> {
+    schema
+        .diagnose(record)
+        .into_iter()
+        .map(|error| {
+            let field = schema.fields.iter().find(|field| field.key == error.key);
+            let field = field.expect("every error is of a field of the schema");
+            PassportError {
+                kind: ErrorKind::of(&error, &field.rule),
+                error,
+            }
+        })
+        .collect()
+}
+
+/// Reasons that passports of a batch were rejected.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub nr_records: usize,
+    pub nr_valid: usize,
+    /// Number of errors of each kind. A record with two missing fields
+    /// counts twice.
+    pub counts: BTreeMap<ErrorKind, usize>,
+    /// Number of errors of each kind and key.
+    pub keys: BTreeMap<(ErrorKind, String), usize>,
+}
+
+impl Summary {
+    /// Add the errors of one record, as returned by `diagnose`.
+    pub fn add(&mut self, errors: &[PassportError]) {
+        self.nr_records += 1;
+        if errors.is_empty() {
+            self.nr_valid += 1;
+        }
+        for e in errors {
+            *self.counts.entry(e.kind).or_insert(0) += 1;
+            *self.keys.entry((e.kind, e.error.key.clone())).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Diagnose every record, and sum up the errors.
+pub fn summarize(v: &[&str], schema: &Schema) -> Summary {
+    let mut summary = Summary::default();
+    for record in v {
+        summary.add(&diagnose(record, schema));
+    }
+    summary
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} passports valid",
+            self.nr_valid, self.nr_records
+        )?;
+        for (kind, count) in &self.counts {
+            writeln!(f, "{:>6} {}", count, kind.name())?;
+            let keys = self.keys.iter().filter(|((x, _), _)| x == kind);
+            for ((_, key), count) in keys {
+                writeln!(f, "{:>10} {}", count, key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_diagnose() {
+        let record = "byr:2003 iyr:20x0 hgt:190 hcl:123abc\necl:xry pid:01234567";
+        let errors = diagnose(record, Schema::puzzle());
+        let kinds: Vec<ErrorKind> = errors.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ErrorKind::OutOfRange,
+                ErrorKind::NotANumber,
+                ErrorKind::MissingField,
+                ErrorKind::NoUnit,
+                ErrorKind::NoMatch,
+                ErrorKind::UnknownValue,
+                ErrorKind::NoMatch,
+            ]
+        );
+        assert_eq!(errors[1].error.message, "invalid number '20x0'");
+        assert_eq!(errors[2].error.key, "eyr");
+        let e = errors[5].error.locate(record);
+        assert_eq!((e.line, e.column), (2, 5));
+        assert_eq!(e.message, "unknown ecl 'xry'");
+
+        let record = "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:08749970a";
+        let errors = diagnose(record, Schema::puzzle());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ErrorKind::NoMatch);
+
+        let record = "byr:abcd iyr:2012 eyr:2030 hgt:xcm hcl:#623a2f ecl:grn pid:087499704";
+        let kinds: Vec<ErrorKind> = diagnose(record, Schema::puzzle())
+            .iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(kinds, [ErrorKind::NotANumber, ErrorKind::NotANumber]);
+        let record = "byr:1980 iyr:2012 eyr:2030 hgt:200cm hcl:#623a2f ecl:grn pid:087499704";
+        let errors = diagnose(record, Schema::puzzle());
+        assert_eq!(errors[0].kind, ErrorKind::UnitOutOfRange);
+    }
+
+    #[test]
+    fn test_diagnose_custom_schema() {
+        let json = r#"{"fields": [
+            {"key": "byr", "pattern": "[A-Z][a-z]+"},
+            {"key": "hgt", "values": ["low", "high"]},
+            {"key": "pid", "min": 1, "max": 10}
+        ]}"#;
+        let schema = Schema::from_json(json).unwrap();
+        let kinds: Vec<ErrorKind> = diagnose("byr:1980 hgt:74in pid:11", &schema)
+            .iter()
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ErrorKind::NoMatch,
+                ErrorKind::UnknownValue,
+                ErrorKind::OutOfRange
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summarize() {
+        let content = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+        let summary = summarize(&parse(content).unwrap(), Schema::puzzle());
+        assert_eq!((summary.nr_records, summary.nr_valid), (3, 1));
+        assert_eq!(summary.counts[&ErrorKind::MissingField], 1);
+        assert_eq!(
+            summary.keys[&(ErrorKind::MissingField, String::from("byr"))],
+            1
+        );
+        assert_eq!(
+            summary.to_string(),
+            "1 of 3 passports valid
+     1 missing field
+         1 byr
+     1 out of range
+         1 eyr
+     1 no unit
+         1 hgt
+     1 no pattern match
+         1 pid
+"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Write accepted passports as JSON, CSV or in the batch file format, and
+//! read them back.
+
+use crate::{Passport, Schema};
+use common::ParseError;
+use serde_json::{json, Value};
+use std::fmt;
+use std::io::{self, Write};
+
+const COLUMNS: [&str; 9] = [
+    "byr", "iyr", "eyr", "hgt", "hgt_unit", "hcl", "ecl", "pid", "cid",
+];
+
+/// Format of `write_passports` and `read_passports`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// Records of `key:value` fields separated by blank lines, like the
+    /// puzzle input.
+    Batch,
+    /// A header and one row per passport, with an empty cid if there is none.
+    Csv,
+    /// One JSON object per line for each passport, with a null cid if there
+    /// is none.
+    Json,
+}
+
+impl Format {
+    /// Format from its name: `batch`, `csv` or `json`.
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "batch" => Some(Format::Batch),
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The passport as a record of the batch file, on one line.
+impl fmt::Display for Passport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "byr:{} iyr:{} eyr:{} hgt:{}{} hcl:#{} ecl:{} pid:{}",
+            self.byr,
+            self.iyr,
+            self.eyr,
+            self.hgt,
+            self.hgt_unit.name(),
+            self.hcl,
+            self.ecl.name(),
+            self.pid
+        )?;
+        if let Some(cid) = &self.cid {
+            write!(f, " cid:{}", cid)?;
+        }
+        Ok(())
+    }
+}
+
+impl Passport {
+    /// The fields as strings, in the order of `COLUMNS`.
+    fn columns(&self) -> [String; 9] {
+        [
+            self.byr.to_string(),
+            self.iyr.to_string(),
+            self.eyr.to_string(),
+            self.hgt.to_string(),
+            String::from(self.hgt_unit.name()),
+            self.hcl.clone(),
+            String::from(self.ecl.name()),
+            self.pid.clone(),
+            self.cid.clone().unwrap_or_default(),
+        ]
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "byr": self.byr,
+            "iyr": self.iyr,
+            "eyr": self.eyr,
+            "hgt": self.hgt,
+            "hgt_unit": self.hgt_unit.name(),
+            "hcl": self.hcl,
+            "ecl": self.ecl.name(),
+            "pid": self.pid,
+            "cid": self.cid,
+        })
+    }
+
+    /// Passport from the fields of `to_json`. It must follow the puzzle rules.
+    pub fn from_json(value: &Value) -> Result<Passport, String> {
+        let mut fields = Vec::new();
+        for key in COLUMNS {
+            let field = match value.get(key) {
+                None | Some(Value::Null) => None,
+                Some(Value::String(x)) => Some(x.clone()),
+                Some(Value::Number(x)) => Some(x.to_string()),
+                Some(_) => return Err(format!("{} must be a string or a number", key)),
+            };
+            fields.push(field);
+        }
+        from_columns(&fields)
+    }
+}
+
+/// Passport from fields in the order of `COLUMNS`, checked by building its
+/// batch file record and parsing it.
+fn from_columns(fields: &[Option<String>]) -> Result<Passport, String> {
+    let mut record = String::new();
+    for (key, field) in COLUMNS.iter().zip(fields) {
+        if let Some(value) = field {
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                return Err(format!("invalid {} '{}'", key, value));
+            }
+        }
+    }
+    for (i, (key, field)) in COLUMNS.iter().zip(fields).enumerate() {
+        let value = match (*key, field) {
+            ("hgt_unit", _) | (_, None) => continue,
+            ("hgt", Some(hgt)) => format!("{}{}", hgt, fields[i + 1].as_deref().unwrap_or("")),
+            ("hcl", Some(hcl)) => format!("#{}", hcl),
+            (_, Some(value)) => value.clone(),
+        };
+        record.push_str(&format!(" {}:{}", key, value));
+    }
+    Passport::parse(&record).map_err(|e| e.message)
+}
+
+/// Write the passports to `out` in the given format.
+pub fn write_passports(
+    passports: &[Passport],
+    format: Format,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    match format {
+        Format::Batch => {
+            for (i, passport) in passports.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "{}", passport)?;
+            }
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(COLUMNS)?;
+            for passport in passports {
+                writer.write_record(passport.columns())?;
+            }
+            writer.flush()?;
+        }
+        Format::Json => {
+            for passport in passports {
+                writeln!(out, "{}", passport.to_json())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read passports written by `write_passports`. Every passport must follow
+/// the puzzle rules.
+pub fn read_passports(content: &str, format: Format) -> Result<Vec<Passport>, ParseError> {
+    match format {
+        Format::Batch => crate::parse(content)?
+            .iter()
+            .map(|record| match Schema::puzzle().diagnose(record).first() {
+                Some(e) => Err(e.locate(content)),
+                None => Passport::parse(record),
+            })
+            .collect(),
+        Format::Csv => {
+            let mut reader = csv::Reader::from_reader(content.as_bytes());
+            let header = reader
+                .headers()
+                .map_err(|e| ParseError::new(1, 1, e.to_string()))?;
+            if header.iter().ne(COLUMNS) {
+                return Err(ParseError::new(
+                    1,
+                    1,
+                    format!("expected the columns {}", COLUMNS.join(",")),
+                ));
+            }
+            let mut passports = Vec::new();
+            for row in reader.records() {
+                let row = row.map_err(|e| {
+                    let line = e.position().map_or(1, |x| x.line() as usize);
+                    ParseError::new(line, 1, e.to_string())
+                })?;
+                let line = row.position().map_or(1, |x| x.line() as usize);
+                let fields: Vec<Option<String>> = row
+                    .iter()
+                    .map(|x| match x {
+                        "" => None,
+                        x => Some(String::from(x)),
+                    })
+                    .collect();
+                passports.push(from_columns(&fields).map_err(|e| ParseError::new(line, 1, e))?);
+            }
+            Ok(passports)
+        }
+        Format::Json => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let value: Value = serde_json::from_str(line)
+                    .map_err(|e| ParseError::at(content, line, e.to_string()))?;
+                Passport::from_json(&value).map_err(|e| ParseError::at(content, line, e))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const BATCH: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022";
+
+    fn passports() -> Vec<Passport> {
+        parse(BATCH)
+            .unwrap()
+            .iter()
+            .map(|x| Passport::parse(x).unwrap())
+            .collect()
+    }
+
+    fn write(format: Format) -> String {
+        let mut out = Vec::new();
+        write_passports(&passports(), format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let passports = passports();
+        assert_eq!(passports[0].cid, None);
+        assert_eq!(passports[1].cid.as_deref(), Some("129"));
+        for format in [Format::Batch, Format::Csv, Format::Json] {
+            assert_eq!(
+                read_passports(&write(format), format),
+                Ok(passports.clone())
+            );
+        }
+        assert_eq!(read_passports("", Format::Batch), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_write() {
+        assert_eq!(
+            write(Format::Batch),
+            "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704
+
+byr:1989 iyr:2014 eyr:2029 hgt:165cm hcl:#a97842 ecl:blu pid:896056539 cid:129
+
+byr:2001 iyr:2015 eyr:2022 hgt:164cm hcl:#888785 ecl:hzl pid:545766238 cid:88
+"
+        );
+        let csv = write(Format::Csv);
+        assert_eq!(
+            csv.lines().take(2).collect::<Vec<_>>(),
+            [
+                "byr,iyr,eyr,hgt,hgt_unit,hcl,ecl,pid,cid",
+                "1980,2012,2030,74,in,623a2f,grn,087499704,"
+            ]
+        );
+        let json = write(Format::Json);
+        let first: Value = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+        assert_eq!(first["hgt_unit"], "in");
+        assert_eq!(first["ecl"], "grn");
+        assert_eq!(first["cid"], Value::Null);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let json = write(Format::Json).replace("\"grn\"", "\"xry\"");
+        let e = read_passports(&json, Format::Json).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (1, "unknown ecl 'xry'"));
+        let json = write(Format::Json).replace("\"087499704\"", "\"0874 99704\"");
+        assert!(read_passports(&json, Format::Json).is_err());
+
+        let csv = write(Format::Csv).replace(",165,", ",200,");
+        let e = read_passports(&csv, Format::Csv).unwrap_err();
+        assert_eq!(
+            (e.line, e.message.as_str()),
+            (3, "height must be 150-193cm or 59-76in")
+        );
+        assert!(read_passports("byr,iyr\n1980,2012\n", Format::Csv).is_err());
+
+        let batch = write(Format::Batch).replace("byr:1989", "byr:1900");
+        let e = read_passports(&batch, Format::Batch).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 5));
+    }
+}
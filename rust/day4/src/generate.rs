@@ -0,0 +1,148 @@
+//! Random passport batch files, where each passport is built to be valid, to
+//! miss a required field, or to have one invalid value.
+
+use common::{Generated, Rng};
+
+const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+fn digits(rng: &mut Rng, n: usize) -> String {
+    (0..n)
+        .map(|_| (b'0' + rng.below(10) as u8) as char)
+        .collect()
+}
+
+fn hex_digits(rng: &mut Rng, n: usize) -> String {
+    (0..n)
+        .map(|_| *rng.choose(b"0123456789abcdef") as char)
+        .collect()
+}
+
+fn valid_value(rng: &mut Rng, key: &str) -> String {
+    match key {
+        "byr" => rng.range(1920..=2002).to_string(),
+        "iyr" => rng.range(2010..=2020).to_string(),
+        "eyr" => rng.range(2020..=2030).to_string(),
+        "hgt" if rng.chance(50) => format!("{}cm", rng.range(150..=193)),
+        "hgt" => format!("{}in", rng.range(59..=76)),
+        "hcl" => format!("#{}", hex_digits(rng, 6)),
+        "ecl" => rng.choose(&EYE_COLORS).to_string(),
+        "pid" => digits(rng, 9),
+        _ => rng.range(1..=999).to_string(),
+    }
+}
+
+fn invalid_value(rng: &mut Rng, key: &str) -> String {
+    let year = |rng: &mut Rng, min: i64, max: i64| loop {
+        let year = rng.range(1900..=2040);
+        if year < min || year > max {
+            return year.to_string();
+        }
+    };
+    match key {
+        "byr" => year(rng, 1920, 2002),
+        "iyr" => year(rng, 2010, 2020),
+        "eyr" => year(rng, 2020, 2030),
+        "hgt" => match rng.below(3) {
+            0 => format!("{}cm", rng.range(100..=149)),
+            1 => format!("{}in", rng.range(77..=99)),
+            _ => rng.range(59..=193).to_string(),
+        },
+        "hcl" => match rng.below(3) {
+            0 => hex_digits(rng, 6),
+            1 => format!("#{}", hex_digits(rng, 5)),
+            _ => format!("#{}z", hex_digits(rng, 5)),
+        },
+        "ecl" => loop {
+            let color: String = (0..3)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect();
+            if !EYE_COLORS.contains(&color.as_str()) {
+                break color;
+            }
+        },
+        _ => match rng.below(3) {
+            0 => digits(rng, 8),
+            1 => digits(rng, 10),
+            _ => format!("{}a", digits(rng, 8)),
+        },
+    }
+}
+
+enum Kind {
+    Valid,
+    Missing(usize),
+    Invalid(usize),
+}
+
+fn passport(rng: &mut Rng, kind: &Kind) -> String {
+    let mut fields: Vec<String> = Vec::new();
+    for (i, key) in REQUIRED.iter().enumerate() {
+        let value = match kind {
+            Kind::Missing(missing) if *missing == i => continue,
+            Kind::Invalid(invalid) if *invalid == i => invalid_value(rng, key),
+            _ => valid_value(rng, key),
+        };
+        fields.push(format!("{}:{}", key, value));
+    }
+    if rng.chance(50) {
+        fields.push(format!("cid:{}", valid_value(rng, "cid")));
+    }
+    rng.shuffle(&mut fields);
+
+    let mut record = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            record.push(if rng.chance(30) { '\n' } else { ' ' });
+        }
+        record += field;
+    }
+    record
+}
+
+/// Batch file with `size` passports.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let mut records = Vec::new();
+    let mut nr_complete = 0;
+    let mut nr_valid = 0;
+    for _ in 0..size {
+        let kind = match rng.below(3) {
+            0 => Kind::Valid,
+            1 => Kind::Missing(rng.below(REQUIRED.len())),
+            _ => Kind::Invalid(rng.below(REQUIRED.len())),
+        };
+        match kind {
+            Kind::Valid => {
+                nr_complete += 1;
+                nr_valid += 1;
+            }
+            Kind::Invalid(_) => nr_complete += 1,
+            Kind::Missing(_) => {}
+        }
+        records.push(passport(&mut rng, &kind));
+    }
+    Generated {
+        input: records.join("\n\n"),
+        part1: nr_complete.into(),
+        part2: nr_valid.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day4;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let generated = generate(seed, 200);
+            let input = Day4::parse(&generated.input).unwrap();
+            assert_eq!(input.len(), 200);
+            assert_eq!(Day4::part1(&input), generated.part1);
+            assert_eq!(Day4::part2(&input), generated.part2);
+        }
+    }
+}
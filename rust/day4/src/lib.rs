@@ -0,0 +1,201 @@
+//! Solutions to 2020: Advent of Code day 4
+//! By Peter Fornwall
+
+use common::{Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+pub mod diagnostics;
+pub mod export;
+pub mod generate;
+pub mod reader;
+pub mod schema;
+
+pub use schema::Schema;
+
+/// Unit of a passport height.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HeightUnit {
+    Cm,
+    Inch,
+}
+
+impl HeightUnit {
+    /// The unit as written after the height, `cm` or `in`.
+    pub fn name(self) -> &'static str {
+        match self {
+            HeightUnit::Cm => "cm",
+            HeightUnit::Inch => "in",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<HeightUnit> {
+        match name {
+            "cm" => Some(HeightUnit::Cm),
+            "in" => Some(HeightUnit::Inch),
+            _ => None,
+        }
+    }
+}
+
+/// The allowed passport eye colors.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EyeColor {
+    Amb,
+    Blu,
+    Brn,
+    Gry,
+    Grn,
+    Hzl,
+    Oth,
+}
+
+impl EyeColor {
+    /// The color as written in the batch file, like `amb`.
+    pub fn name(self) -> &'static str {
+        match self {
+            EyeColor::Amb => "amb",
+            EyeColor::Blu => "blu",
+            EyeColor::Brn => "brn",
+            EyeColor::Gry => "gry",
+            EyeColor::Grn => "grn",
+            EyeColor::Hzl => "hzl",
+            EyeColor::Oth => "oth",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<EyeColor> {
+        match name {
+            "amb" => Some(EyeColor::Amb),
+            "blu" => Some(EyeColor::Blu),
+            "brn" => Some(EyeColor::Brn),
+            "gry" => Some(EyeColor::Gry),
+            "grn" => Some(EyeColor::Grn),
+            "hzl" => Some(EyeColor::Hzl),
+            "oth" => Some(EyeColor::Oth),
+            _ => None,
+        }
+    }
+}
+
+/// A passport where all fields are present and valid.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Passport {
+    pub byr: i32, // (Birth Year)
+    pub iyr: i32, // (Issue Year)
+    pub eyr: i32, // (Expiration Year)
+    pub hgt: i32, // (Height)
+    pub hgt_unit: HeightUnit,
+    pub hcl: String,         // (Hair Color)
+    pub ecl: EyeColor,       // (Eye Color)
+    pub pid: String,         // (Passport ID)
+    pub cid: Option<String>, // Optional cid (Country ID)
+}
+
+// The values are checked by the puzzle schema before they are converted.
+fn parse_passport(h: &HashMap<&str, &str>) -> Passport {
+    let nr = |key: &str| h[key].parse().unwrap();
+    let hgt_str = h["hgt"];
+    let (hgt_nr_str, unit_str) = hgt_str.split_at(hgt_str.len() - 2);
+    Passport {
+        byr: nr("byr"),
+        iyr: nr("iyr"),
+        eyr: nr("eyr"),
+        hgt: hgt_nr_str.parse().unwrap(),
+        hgt_unit: HeightUnit::from_name(unit_str).unwrap(),
+        hcl: String::from(&h["hcl"][1..]),
+        ecl: EyeColor::from_name(h["ecl"]).unwrap(),
+        pid: String::from(h["pid"]),
+        cid: h.get("cid").map(|x| String::from(*x)),
+    }
+}
+
+// Tokens without a ':' are rejected by parse(), so they are skipped here.
+pub(crate) fn create_passport_map(s: &str) -> HashMap<&str, &str> {
+    s.split_whitespace()
+        .filter_map(|x| {
+            let index = x.find(':')?;
+            Some((&x[..index], &x[(index + 1)..]))
+        })
+        .collect()
+}
+
+impl Passport {
+    pub fn has_fields(s: &str) -> bool {
+        Schema::puzzle().has_fields(s)
+    }
+
+    /// Parse and validate a passport record with the rules of the puzzle.
+    /// Error positions are relative to `s`.
+    pub fn parse(s: &str) -> Result<Passport, ParseError> {
+        Schema::puzzle().validate(s)?;
+        Ok(parse_passport(&create_passport_map(s)))
+    }
+}
+
+/// Number of records with all required fields.
+pub fn part1(v: &[&str]) -> usize {
+    nr_complete(v, Schema::puzzle())
+}
+
+/// Number of records with all required fields, and valid values.
+pub fn part2(v: &[&str]) -> usize {
+    nr_valid(v, Schema::puzzle())
+}
+
+/// Number of records with all fields required by the schema.
+pub fn nr_complete(v: &[&str], schema: &Schema) -> usize {
+    v.iter().filter(|x| schema.has_fields(x)).count()
+}
+
+/// Number of records that follow the schema.
+pub fn nr_valid(v: &[&str], schema: &Schema) -> usize {
+    v.iter().filter(|x| schema.validate(x).is_ok()).count()
+}
+
+/// Split the batch file into passport records, separated by lines that are
+/// empty or only whitespace. Lines may end with `\r\n`. See
+/// `reader::RecordReader` for files that are too large to read at once.
+pub fn parse(content: &str) -> Result<Vec<&str>, ParseError> {
+    let mut records = Vec::new();
+    let mut record: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in content.split('\n') {
+        let start = offset;
+        offset += line.len() + 1;
+        let line = line.trim_end();
+        if line.is_empty() {
+            if let Some((start, end)) = record.take() {
+                records.push(&content[start..end]);
+            }
+            continue;
+        }
+        if let Some(token) = line.split_whitespace().find(|x| !x.contains(':')) {
+            return Err(ParseError::at(content, token, "expected key:value"));
+        }
+        let end = start + line.len();
+        record = Some(record.map_or((start, end), |(start, _)| (start, end)));
+    }
+    if let Some((start, end)) = record {
+        records.push(&content[start..end]);
+    }
+    Ok(records)
+}
+
+/// Day 4 as a `Solution`, as used by the aoc runner.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
@@ -1,199 +1,115 @@
 //! Solutions to 2020: Advent of Code day 4
 //! By Peter Fornwall
-
-use std::collections::HashMap;
-use std::fs;
-
-#[derive(PartialEq)]
-enum HeightUnit {
-    Cm,
-    Inch,
-}
-
-#[derive(PartialEq, Debug)]
-enum EyeColor {
-    Amb,
-    Blu,
-    Brn,
-    Gry,
-    Grn,
-    Hzl,
-    Oth,
-}
-
-struct Passport {
-    byr: i32, // (Birth Year)
-    iyr: i32, // (Issue Year)
-    eyr: i32, // (Expiration Year)
-    hgt: i32, // (Height)
-    hgt_unit: HeightUnit,
-    hcl: String,   // (Hair Color)
-    ecl: EyeColor, // (Eye Color)
-    pid: String,   // (Passport ID)
-    cid: String,   // Optional cid (Country ID)
+//!
+//! Usage: day4 [INPUT] [--schema PATH] [--diagnose]
+//!        day4 [INPUT] --export batch|csv|json
+//!
+//! --schema validates the passports against the rules of a TOML or JSON
+//! schema instead of the puzzle rules in schema/passport.toml.
+//!
+//! --diagnose prints every error of every rejected passport, and a summary
+//! of the reasons.
+//!
+//! --export writes the passports that follow the puzzle rules to stdout. It
+//! can not be combined with --schema, since an exported passport has the
+//! fields of the puzzle.
+
+use day4::reader::RecordReader;
+use day4::{diagnostics, export, Passport, Schema};
+use std::env;
+use std::io;
+use std::path::Path;
+use std::process;
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: day4 [INPUT] [--schema PATH] [--diagnose]");
+    eprintln!("       day4 [INPUT] --export batch|csv|json");
+    process::exit(2);
 }
 
-fn parse_nr_range(key: &str, min: i32, max: i32) -> Result<i32, ()> {
-    let value = key.parse::<i32>().map_err(|_x| ())?;
-    if value >= min && value <= max {
-        return Ok(value);
+fn main() {
+    let mut input = None;
+    let mut schema = None;
+    let mut diagnose = false;
+    let mut export = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--schema" => {
+                let path = args
+                    .next()
+                    .unwrap_or_else(|| exit_with_usage("Missing value for --schema"));
+                schema = Some(Schema::load(Path::new(&path)).unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }));
+            }
+            "--diagnose" => diagnose = true,
+            "--export" => {
+                let name = args
+                    .next()
+                    .unwrap_or_else(|| exit_with_usage("Missing value for --export"));
+                export = Some(
+                    export::Format::from_name(&name)
+                        .unwrap_or_else(|| exit_with_usage(&format!("Unknown format: {}", name))),
+                );
+            }
+            _ => input = Some(arg),
+        }
     }
-    return Err(());
-}
-
-fn parse_map_value_nr_range(
-    h: &HashMap<&str, &str>,
-    key: &str,
-    min: i32,
-    max: i32,
-) -> Result<i32, ()> {
-    if h.contains_key(key) {
-        return parse_nr_range(h[key], min, max);
+    if export.is_some() && (schema.is_some() || diagnose) {
+        exit_with_usage("--export can not be combined with --schema or --diagnose");
     }
-    return Err(());
-}
-
-fn parse_height(h: &HashMap<&str, &str>) -> Result<(i32, HeightUnit), ()> {
-    if let Some(str_value) = h.get("hgt") {
-        let len = str_value.len();
-        if len > 2 {
-            let hgt_value_str = &str_value[0..(len - 2)];
-            match &str_value[(len - 2)..] {
-                "cm" => {
-                    let hgt = parse_nr_range(hgt_value_str, 150, 193)?;
-                    return Ok((hgt, HeightUnit::Cm));
-                }
-                "in" => {
-                    let hgt = parse_nr_range(hgt_value_str, 59, 76)?;
-                    return Ok((hgt, HeightUnit::Inch));
-                }
-                _ => {
-                    return Err(());
-                }
-            };
-        }
+    let source = common::InputSource::from_arg(input.as_deref(), 4);
+    let records = RecordReader::new(common::open_or_exit(&source)).map(|record| {
+        record.unwrap_or_else(|e| {
+            eprintln!("{}: {}", source, e);
+            process::exit(1);
+        })
+    });
+
+    if let Some(format) = export {
+        let passports: Vec<Passport> = records
+            .filter_map(|x| Passport::parse(&x.text).ok())
+            .collect();
+        let stdout = io::stdout();
+        export::write_passports(&passports, format, &mut stdout.lock()).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        });
+        return;
     }
-    return Err(());
-}
-
-fn parse_hair_color(h: &HashMap<&str, &str>) -> Result<String, ()> {
-    if let Some(hcl_str) = h.get("hcl") {
-        if hcl_str.len() == 7 && hcl_str.starts_with("#") {
-            let hcl_nr_str = &hcl_str[1..];
-            if hcl_nr_str
-                .chars()
-                .filter(|x| (*x >= '0' && *x <= '9') || (*x >= 'a' && *x <= 'f'))
-                .count()
-                == 6
-            {
-                return Ok(String::from(hcl_nr_str));
+    let schema = schema.as_ref().unwrap_or_else(|| Schema::puzzle());
+    if diagnose {
+        let mut summary = diagnostics::Summary::default();
+        for record in records {
+            let errors = diagnostics::diagnose(&record.text, schema);
+            for e in &errors {
+                let error = record.locate(e.error.locate(&record.text));
+                println!(
+                    "line {}, column {}: {}: {}",
+                    error.line,
+                    error.column,
+                    e.kind.name(),
+                    error.message
+                );
             }
+            summary.add(&errors);
         }
+        print!("{}", summary);
+        return;
     }
-    return Err(());
-}
-
-fn parse_eye_color(h: &HashMap<&str, &str>) -> Result<EyeColor, ()> {
-    if let Some(ecl_str) = h.get("ecl") {
-        return match *ecl_str {
-            "amb" => Some(EyeColor::Amb),
-            "blu" => Some(EyeColor::Blu),
-            "brn" => Some(EyeColor::Brn),
-            "gry" => Some(EyeColor::Gry),
-            "grn" => Some(EyeColor::Grn),
-            "hzl" => Some(EyeColor::Hzl),
-            "oth" => Some(EyeColor::Oth),
-            _ => None,
+    let mut result_p1 = 0;
+    let mut result_p2 = 0;
+    for record in records {
+        if schema.has_fields(&record.text) {
+            result_p1 += 1;
         }
-        .ok_or(());
-    }
-    return Err(());
-}
-
-fn parse_pid(h: &HashMap<&str, &str>) -> Result<String, ()> {
-    if let Some(pid_str) = h.get("pid") {
-        if pid_str.len() == 9 {
-            if pid_str.chars().all(|x| x.is_ascii_digit()) {
-                return Ok(String::from(*pid_str));
-            }
+        if schema.validate(&record.text).is_ok() {
+            result_p2 += 1;
         }
     }
-    return Err(());
-}
-
-fn parse_passport(h: &HashMap<&str, &str>) -> Result<Passport, ()> {
-    let byr: i32 = parse_map_value_nr_range(h, "byr", 1920, 2002)?;
-    let iyr: i32 = parse_map_value_nr_range(h, "iyr", 2010, 2020)?;
-    let eyr: i32 = parse_map_value_nr_range(h, "eyr", 2020, 2030)?;
-    let (hgt, hgt_unit) = parse_height(h)?;
-    let hcl: String = parse_hair_color(h)?;
-    let ecl: EyeColor = parse_eye_color(h)?;
-    let pid: String = parse_pid(h)?;
-    let cid = String::from(*h.get("cid").unwrap_or(&""));
-
-    let passport = Passport {
-        byr,
-        iyr,
-        eyr,
-        hgt,
-        hgt_unit,
-        hcl,
-        ecl,
-        pid,
-        cid,
-    };
-    return Ok(passport);
-}
-
-fn create_passport_map(s: &str) -> HashMap<&str, &str> {
-    return s
-        .split(|c| c == ' ' || c == '\n')
-        .map(|x| {
-            let key_value: Vec<&str> = x.split(":").collect();
-            return (key_value[0], key_value[1]);
-        })
-        .collect();
-}
-
-impl Passport {
-    fn has_fields(s: &str) -> bool {
-        let h: HashMap<&str, &str> = create_passport_map(s);
-        let v = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
-        return v.iter().all(|x| h.contains_key(x));
-    }
-
-    fn parse(s: &str) -> Result<Passport, ()> {
-        let h: HashMap<&str, &str> = create_passport_map(s);
-        return parse_passport(&h);
-    }
-}
-
-pub fn part1(v: &Vec<&str>) -> usize {
-    let nr = v
-        .iter()
-        .map(|x| Passport::has_fields(x))
-        .filter(|x| *x == true)
-        .count();
-
-    println!("Part1: {}", nr);
-    return nr;
-}
-
-pub fn part2(v: &Vec<&str>) -> usize {
-    let nr = v
-        .iter()
-        .map(|x| Passport::parse(x))
-        .filter_map(Result::ok)
-        .count();
-
-    println!("Part2: {}", nr);
-    return nr;
-}
-
-fn main() {
-    let content = fs::read_to_string("../../inputs/day4_input.txt").expect("Cannot open file!");
-    let v: Vec<&str> = content.trim().split("\n\n").collect();
-
-    let _result_p1 = part1(&v);
-    let _result_p2 = part2(&v);
+    println!("Part1: {}", result_p1);
+    println!("Part2: {}", result_p2);
 }
@@ -0,0 +1,189 @@
+//! Streaming reader of batch file records, for inputs too large to read at
+//! once.
+
+use common::ParseError;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// A record of the batch file, with the lines of its fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// Line of the input where the record starts, starting at 1.
+    pub line: usize,
+    /// The lines of the record, without line endings or trailing whitespace.
+    pub text: String,
+}
+
+impl Record {
+    /// An error located in `text`, located in the whole input instead.
+    pub fn locate(&self, e: ParseError) -> ParseError {
+        ParseError::new(self.line + e.line - 1, e.column, e.message)
+    }
+}
+
+/// Error of `RecordReader`.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{}", e),
+            ReadError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+/// Iterator over the records of a batch file, read one line at a time.
+///
+/// Records are separated by lines that are empty or only whitespace. Lines
+/// may end with `\r\n`. A token without a `:` makes its whole record an
+/// error, located in the input, and reading goes on with the next record.
+pub struct RecordReader<R> {
+    reader: R,
+    line: usize,
+    buf: String,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    pub fn new(reader: R) -> Self {
+        RecordReader {
+            reader,
+            line: 0,
+            buf: String::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for RecordReader<R> {
+    type Item = Result<Record, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record: Option<Record> = None;
+        let mut error = None;
+        loop {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => break,
+                Ok(_) => self.line += 1,
+                Err(e) => return Some(Err(e.into())),
+            }
+            let line = self.buf.trim_end();
+            if line.is_empty() {
+                if record.is_some() || error.is_some() {
+                    break;
+                }
+                continue;
+            }
+            if error.is_some() {
+                continue;
+            }
+            if let Some(token) = line.split_whitespace().find(|x| !x.contains(':')) {
+                let e = ParseError::at(line, token, "expected key:value");
+                error = Some(ParseError::new(self.line, e.column, e.message));
+                continue;
+            }
+            match &mut record {
+                Some(record) => {
+                    record.text.push('\n');
+                    record.text.push_str(line);
+                }
+                None => {
+                    record = Some(Record {
+                        line: self.line,
+                        text: String::from(line),
+                    })
+                }
+            }
+        }
+        match error {
+            Some(e) => Some(Err(ReadError::Parse(e))),
+            None => record.map(Ok),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, part1, part2};
+
+    const EXAMPLE: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+
+    fn read(content: &str) -> Vec<Result<Record, String>> {
+        RecordReader::new(content.as_bytes())
+            .map(|x| x.map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_reader() {
+        let records: Vec<Record> = read(EXAMPLE).into_iter().map(|x| x.unwrap()).collect();
+        let lines: Vec<usize> = records.iter().map(|x| x.line).collect();
+        assert_eq!(lines, [1, 4, 7, 12]);
+        let texts: Vec<&str> = records.iter().map(|x| x.text.as_str()).collect();
+        assert_eq!(texts, parse(EXAMPLE).unwrap());
+
+        // Windows line endings, trailing whitespace and whitespace lines.
+        let messy = EXAMPLE
+            .replace('\n', " \r\n")
+            .replace("\r\n \r\n", "\r\n\t\r\n\r\n");
+        let messy_records: Vec<Record> = read(&messy).into_iter().map(|x| x.unwrap()).collect();
+        let texts: Vec<&str> = messy_records.iter().map(|x| x.text.as_str()).collect();
+        assert_eq!((part1(&texts), part2(&texts)), (2, 2));
+        assert_eq!(messy_records[1].line, 5);
+        let v = parse(&messy).unwrap();
+        assert_eq!((v.len(), part1(&v), part2(&v)), (4, 2, 2));
+    }
+
+    #[test]
+    fn test_malformed_token() {
+        let content = "a:1 b:2\n\nc:3\n  d:4 oops e:5\ng:7 x\n\nf:6\n";
+        let results = read(content);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().text, "a:1 b:2");
+        assert_eq!(
+            results[1],
+            Err(String::from("line 4, column 7: expected key:value"))
+        );
+        assert_eq!(
+            results[2],
+            Ok(Record {
+                line: 7,
+                text: String::from("f:6")
+            })
+        );
+
+        let record = Record {
+            line: 10,
+            text: String::from("a:1\nb:x"),
+        };
+        let e = record.locate(ParseError::at(&record.text, &record.text[6..], "bad"));
+        assert_eq!((e.line, e.column), (11, 3));
+    }
+}
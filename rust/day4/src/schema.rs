@@ -0,0 +1,349 @@
+//! Declarative validation rules for batch file records, loaded from TOML or
+//! JSON. See `schema/passport.toml` for the format and the puzzle rules.
+
+use crate::create_passport_map;
+use common::ParseError;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const PASSPORT_SCHEMA: &str = include_str!("../schema/passport.toml");
+
+/// Rule for the value of a field.
+#[derive(Clone, Debug)]
+pub enum Rule {
+    /// Any value.
+    Any,
+    /// A number from `min` to `max`.
+    Range { min: i64, max: i64 },
+    /// A number followed by one of the units, in the range of that unit, as
+    /// (unit, min, max).
+    Units(Vec<(String, i64, i64)>),
+    /// Matches the regular expression, which is anchored at both ends.
+    Pattern(Regex),
+    /// One of the values.
+    OneOf(Vec<String>),
+}
+
+/// Rule for one field of a record.
+#[derive(Clone, Debug)]
+pub struct FieldRule {
+    pub key: String,
+    pub required: bool,
+    pub rule: Rule,
+    /// Error message used instead of the one of the rule.
+    pub message: Option<String>,
+}
+
+/// The fields of a record and their rules.
+#[derive(Clone, Debug)]
+pub struct Schema {
+    pub fields: Vec<FieldRule>,
+}
+
+fn get_i64(table: &toml::Table, name: &str, key: &str) -> Result<Option<i64>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(toml::Value::Integer(nr)) => Ok(Some(*nr)),
+        Some(_) => Err(format!("{}.{} must be an integer", name, key)),
+    }
+}
+
+fn parse_range(name: &str, value: &toml::Value) -> Result<(i64, i64), String> {
+    match value.as_array().map(|x| &x[..]) {
+        Some([toml::Value::Integer(min), toml::Value::Integer(max)]) => Ok((*min, *max)),
+        _ => Err(format!("{} must be [min, max]", name)),
+    }
+}
+
+fn parse_field(table: &toml::Table) -> Result<FieldRule, String> {
+    let key = table
+        .get("key")
+        .and_then(|x| x.as_str())
+        .ok_or("every field needs a key")?;
+    let required = match table.get("required") {
+        None => true,
+        Some(toml::Value::Boolean(required)) => *required,
+        Some(_) => return Err(format!("{}.required must be true or false", key)),
+    };
+    let message = match table.get("message") {
+        None => None,
+        Some(toml::Value::String(message)) => Some(message.clone()),
+        Some(_) => return Err(format!("{}.message must be a string", key)),
+    };
+    let mut rules = Vec::new();
+    let min = get_i64(table, key, "min")?;
+    let max = get_i64(table, key, "max")?;
+    if min.is_some() || max.is_some() {
+        rules.push(Rule::Range {
+            min: min.unwrap_or(i64::MIN),
+            max: max.unwrap_or(i64::MAX),
+        });
+    }
+    if let Some(units) = table.get("units") {
+        let units = units
+            .as_table()
+            .ok_or(format!("{}.units must be a table", key))?;
+        let units = units
+            .iter()
+            .map(|(unit, range)| {
+                let (min, max) = parse_range(&format!("{}.units.{}", key, unit), range)?;
+                Ok((unit.clone(), min, max))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        rules.push(Rule::Units(units));
+    }
+    if let Some(pattern) = table.get("pattern") {
+        let pattern = pattern
+            .as_str()
+            .ok_or(format!("{}.pattern must be a string", key))?;
+        let regex = Regex::new(&format!("^(?:{})$", pattern))
+            .map_err(|e| format!("{}.pattern: {}", key, e))?;
+        rules.push(Rule::Pattern(regex));
+    }
+    if let Some(values) = table.get("values") {
+        let values = values
+            .as_array()
+            .and_then(|values| {
+                values
+                    .iter()
+                    .map(|x| x.as_str().map(String::from))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or(format!("{}.values must be a list of strings", key))?;
+        rules.push(Rule::OneOf(values));
+    }
+    if rules.len() > 1 {
+        return Err(format!("{} can only have one rule", key));
+    }
+    Ok(FieldRule {
+        key: String::from(key),
+        required,
+        rule: rules.pop().unwrap_or(Rule::Any),
+        message,
+    })
+}
+
+impl Schema {
+    /// The passport rules of the puzzle.
+    pub fn puzzle() -> &'static Schema {
+        static PUZZLE: OnceLock<Schema> = OnceLock::new();
+        PUZZLE.get_or_init(|| Schema::from_toml(PASSPORT_SCHEMA).unwrap())
+    }
+
+    pub fn from_toml(content: &str) -> Result<Schema, String> {
+        let value: toml::Value = content.parse().map_err(|e| format!("{}", e))?;
+        Schema::from_value(&value)
+    }
+
+    pub fn from_json(content: &str) -> Result<Schema, String> {
+        let value: toml::Value = serde_json::from_str(content).map_err(|e| format!("{}", e))?;
+        Schema::from_value(&value)
+    }
+
+    /// Read a schema from `path`, as JSON if it ends with `.json` and as TOML
+    /// otherwise.
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+        let schema = match path.extension().and_then(|x| x.to_str()) {
+            Some("json") => Schema::from_json(&content),
+            _ => Schema::from_toml(&content),
+        };
+        schema.map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn from_value(value: &toml::Value) -> Result<Schema, String> {
+        let fields = value
+            .get("fields")
+            .and_then(|x| x.as_array())
+            .ok_or("expected a list of fields")?;
+        let fields = fields
+            .iter()
+            .map(|field| parse_field(field.as_table().ok_or("every field must be a table")?))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Schema { fields })
+    }
+
+    /// True if the record has all required fields.
+    pub fn has_fields(&self, record: &str) -> bool {
+        let h = create_passport_map(record);
+        self.fields
+            .iter()
+            .all(|field| !field.required || h.contains_key(field.key.as_str()))
+    }
+
+    /// Check that the record has all required fields, and that all fields
+    /// follow their rules. Error positions are relative to `record`.
+    pub fn validate(&self, record: &str) -> Result<(), ParseError> {
+        match self.diagnose(record).first() {
+            Some(e) => Err(e.locate(record)),
+            None => Ok(()),
+        }
+    }
+
+    /// Every missing field and every field that breaks its rule, in the
+    /// order of the schema.
+    pub fn diagnose<'a>(&self, record: &'a str) -> Vec<FieldError<'a>> {
+        let h = create_passport_map(record);
+        self.fields
+            .iter()
+            .filter_map(|field| match h.get(field.key.as_str()) {
+                Some(value) => field.check(value).err(),
+                None if field.required => Some(FieldError {
+                    key: field.key.clone(),
+                    failure: Failure::Missing,
+                    token: record,
+                    message: format!("missing field '{}'", field.key),
+                }),
+                None => None,
+            })
+            .collect()
+    }
+}
+
+/// Why a field is not valid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    Missing,
+    NotANumber,
+    OutOfRange,
+    /// The value does not end with any of the units.
+    NoUnit,
+    NoMatch,
+    UnknownValue,
+}
+
+/// A field that is missing, or breaks its rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError<'a> {
+    pub key: String,
+    pub failure: Failure,
+    /// Where the error is: the value, the number in it, or the whole record
+    /// if the field is missing.
+    pub token: &'a str,
+    pub message: String,
+}
+
+impl FieldError<'_> {
+    /// The error as a `ParseError`, located in `content`, which the token
+    /// should be a slice of.
+    pub fn locate(&self, content: &str) -> ParseError {
+        ParseError::at(content, self.token, self.message.clone())
+    }
+}
+
+impl FieldRule {
+    /// Check the value of the field against the rule.
+    pub fn check<'a>(&self, value: &'a str) -> Result<(), FieldError<'a>> {
+        self.check_rule(value)
+            .map_err(|(failure, token, message)| FieldError {
+                key: self.key.clone(),
+                failure,
+                token,
+                message: self.message.clone().unwrap_or(message),
+            })
+    }
+
+    fn check_rule<'a>(&self, value: &'a str) -> Result<(), (Failure, &'a str, String)> {
+        let in_range = |nr_str: &'a str, min: i64, max: i64| match nr_str.parse::<i64>() {
+            Ok(nr) if nr >= min && nr <= max => Ok(()),
+            Ok(nr) => Err((
+                Failure::OutOfRange,
+                nr_str,
+                format!("{} is not in range {}-{}", nr, min, max),
+            )),
+            Err(_) => Err((
+                Failure::NotANumber,
+                nr_str,
+                format!("invalid number '{}'", nr_str),
+            )),
+        };
+        match &self.rule {
+            Rule::Any => Ok(()),
+            Rule::Range { min, max } => in_range(value, *min, *max),
+            Rule::Units(units) => {
+                for (unit, min, max) in units {
+                    if let Some(nr_str) = value.strip_suffix(unit.as_str()) {
+                        return in_range(nr_str, *min, *max);
+                    }
+                }
+                let names: Vec<&str> = units.iter().map(|x| x.0.as_str()).collect();
+                Err((
+                    Failure::NoUnit,
+                    value,
+                    format!("{} must end with {}", self.key, names.join(" or ")),
+                ))
+            }
+            Rule::Pattern(regex) => match regex.is_match(value) {
+                true => Ok(()),
+                false => Err((
+                    Failure::NoMatch,
+                    value,
+                    format!("{} '{}' does not match {}", self.key, value, regex),
+                )),
+            },
+            Rule::OneOf(values) => match values.iter().any(|x| x == value) {
+                true => Ok(()),
+                false => Err((
+                    Failure::UnknownValue,
+                    value,
+                    format!("unknown {} '{}'", self.key, value),
+                )),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_schema() {
+        let schema = Schema::puzzle();
+        assert_eq!(schema.fields.len(), 8);
+        let valid = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f";
+        assert!(schema.has_fields(valid));
+        assert_eq!(schema.validate(valid), Ok(()));
+
+        let e = schema
+            .validate(&valid.replace("74in", "190in"))
+            .unwrap_err();
+        assert_eq!(e.message, "height must be 150-193cm or 59-76in");
+        let e = schema.validate(&valid.replace("grn", "xry")).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 28));
+        assert_eq!(e.message, "unknown ecl 'xry'");
+        let e = schema
+            .validate(&valid.replace("byr:1980", "byr:2003"))
+            .unwrap_err();
+        assert_eq!(e.message, "2003 is not in range 1920-2002");
+        let missing = valid.replace("pid:087499704 ", "");
+        assert!(!schema.has_fields(&missing));
+        assert_eq!(
+            schema.validate(&missing).unwrap_err().message,
+            "missing field 'pid'"
+        );
+    }
+
+    #[test]
+    fn test_custom_schema() {
+        let json = r#"{"fields": [
+            {"key": "name", "pattern": "[A-Z][a-z]+"},
+            {"key": "age", "min": 0, "max": 150},
+            {"key": "note", "required": false}
+        ]}"#;
+        let schema = Schema::from_json(json).unwrap();
+        assert_eq!(schema.validate("name:Ada age:36"), Ok(()));
+        assert_eq!(schema.validate("age:36 note:x name:Ada"), Ok(()));
+        assert!(schema.validate("name:ada age:36").is_err());
+        assert!(schema.validate("name:Ada age:-1").is_err());
+        assert!(!schema.has_fields("name:Ada"));
+
+        assert!(Schema::from_toml("[[fields]]\nmin = 1").is_err());
+        assert!(Schema::from_toml("[[fields]]\nkey = \"a\"\nmin = 1\nvalues = []").is_err());
+        assert!(Schema::from_toml("[[fields]]\nkey = \"a\"\nunits = { cm = [1] }").is_err());
+        assert!(Schema::from_toml("[[fields]]\nkey = \"a\"\npattern = \"(\"").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Random lists of boarding passes for a consecutive range of seats, with one
+//! seat in the middle missing.
+
+use crate::SeatLayout;
+use common::{Generated, Rng};
+
+// Boarding pass of a seat ID of the puzzle plane.
+fn boarding_pass(id: usize) -> String {
+    let seat = SeatLayout::PUZZLE.seat(id);
+    SeatLayout::PUZZLE.encode(seat.row, seat.column).unwrap()
+}
+
+/// List of `size` (3 to 1022) boarding passes.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let size = size.clamp(3, 1022);
+    // The seats are first..=last, except missing.
+    let first = rng.below(1024 - size);
+    let last = first + size;
+    let missing = first + 1 + rng.below(size - 1);
+    let mut passes: Vec<String> = (first..=last)
+        .filter(|&id| id != missing)
+        .map(boarding_pass)
+        .collect();
+    rng.shuffle(&mut passes);
+    Generated {
+        input: passes.join("\n"),
+        part1: last.into(),
+        part2: missing.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day5;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(boarding_pass(357), "FBFBBFFRLR");
+        for seed in 0..20 {
+            let generated = generate(seed, 500);
+            let input = Day5::parse(&generated.input).unwrap();
+            assert_eq!(input.len(), 500);
+            assert_eq!(Day5::part1(&input), generated.part1);
+            assert_eq!(Day5::part2(&input), generated.part2);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Plane layouts: how many rows and columns a plane has, and the letters
+//! that boarding passes use to binary partition them.
+
+use common::ParseError;
+
+/// A seat, by its row and column, and its seat ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Seat {
+    pub row: usize,
+    pub column: usize,
+    pub id: usize,
+}
+
+/// Shape of a plane and how its boarding passes are written. A pass has one
+/// letter per row bit and then one letter per column bit, from the highest
+/// bit, where the second letter of each pair is a set bit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeatLayout {
+    pub row_bits: u32,
+    pub column_bits: u32,
+    /// Letters for the front and the back half of the rows.
+    pub row_letters: (char, char),
+    /// Letters for the left and the right half of the columns.
+    pub column_letters: (char, char),
+}
+
+impl SeatLayout {
+    /// The plane of the puzzle: 128 rows of 8 seats, with passes like
+    /// `FBFBBFFRLR`.
+    pub const PUZZLE: SeatLayout = SeatLayout {
+        row_bits: 7,
+        column_bits: 3,
+        row_letters: ('F', 'B'),
+        column_letters: ('L', 'R'),
+    };
+
+    /// Layout from a spec like `7,3` or `7,3,FB,LR`: the number of row and
+    /// column bits, and optionally the letter pairs, which default to those
+    /// of the puzzle.
+    pub fn from_spec(spec: &str) -> Result<SeatLayout, String> {
+        let parts: Vec<&str> = spec.split(',').map(|x| x.trim()).collect();
+        let bits = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| format!("Invalid number of bits: {}", s))
+        };
+        let letters = |s: &str| match s.chars().collect::<Vec<_>>()[..] {
+            [lower, upper] => Ok((lower, upper)),
+            _ => Err(format!("Expected two letters, like FB: {}", s)),
+        };
+        let mut layout = SeatLayout::PUZZLE;
+        match parts[..] {
+            [rows, columns] => {
+                layout.row_bits = bits(rows)?;
+                layout.column_bits = bits(columns)?;
+            }
+            [rows, columns, row_letters, column_letters] => {
+                layout.row_bits = bits(rows)?;
+                layout.column_bits = bits(columns)?;
+                layout.row_letters = letters(row_letters)?;
+                layout.column_letters = letters(column_letters)?;
+            }
+            _ => return Err(format!("Expected ROW_BITS,COLUMN_BITS[,FB,LR]: {}", spec)),
+        }
+        layout.check()?;
+        Ok(layout)
+    }
+
+    /// Ok if passes of the layout can be decoded, otherwise why not.
+    pub fn check(&self) -> Result<(), String> {
+        if self.row_bits == 0 || self.row_bits + self.column_bits > 32 {
+            return Err(format!(
+                "Expected 1 to 32 bits in all, with at least one row bit, found {} and {}",
+                self.row_bits, self.column_bits
+            ));
+        }
+        for (lower, upper) in [self.row_letters, self.column_letters] {
+            if lower == upper {
+                return Err(format!(
+                    "The letters of a pair must differ: {}{}",
+                    lower, upper
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn nr_rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn nr_columns(&self) -> usize {
+        1 << self.column_bits
+    }
+
+    /// Number of letters of a boarding pass.
+    pub fn pass_len(&self) -> usize {
+        (self.row_bits + self.column_bits) as usize
+    }
+
+    /// Seat ID of a seat: the row times the number of columns, plus the
+    /// column.
+    pub fn seat_id(&self, row: usize, column: usize) -> usize {
+        row * self.nr_columns() + column
+    }
+
+    /// The seat of a seat ID below `nr_rows() * nr_columns()`.
+    pub fn seat(&self, id: usize) -> Seat {
+        Seat {
+            row: id / self.nr_columns(),
+            column: id % self.nr_columns(),
+            id,
+        }
+    }
+
+    /// Check that `pass`, a slice of `content`, has the letters of the
+    /// layout. Errors are located in `content`.
+    pub fn check_pass<'a>(&self, content: &str, pass: &'a str) -> Result<&'a str, ParseError> {
+        for (i, (pos, ch)) in pass.char_indices().enumerate() {
+            let (lower, upper) = if i < self.row_bits as usize {
+                self.row_letters
+            } else {
+                self.column_letters
+            };
+            if i >= self.pass_len() || (ch != lower && ch != upper) {
+                return Err(ParseError::at(
+                    content,
+                    &pass[pos..],
+                    format!("unexpected character '{}'", ch),
+                ));
+            }
+        }
+        if pass.chars().count() < self.pass_len() {
+            return Err(ParseError::after(content, pass, "boarding pass too short"));
+        }
+        Ok(pass)
+    }
+
+    /// The seat of a boarding pass. Errors are located in `pass`.
+    pub fn decode(&self, pass: &str) -> Result<Seat, ParseError> {
+        self.check_pass(pass, pass)?;
+        let id = pass.chars().enumerate().fold(0, |id, (i, ch)| {
+            let upper = if i < self.row_bits as usize {
+                self.row_letters.1
+            } else {
+                self.column_letters.1
+            };
+            id * 2 + (ch == upper) as usize
+        });
+        Ok(self.seat(id))
+    }
+
+    /// Boarding pass of a seat, or None if the plane has no such seat.
+    pub fn encode(&self, row: usize, column: usize) -> Option<String> {
+        if row >= self.nr_rows() || column >= self.nr_columns() {
+            return None;
+        }
+        let letters = |value: usize, bits: u32, (lower, upper): (char, char)| {
+            (0..bits).rev().map(move |bit| {
+                if (value >> bit) & 1 == 1 {
+                    upper
+                } else {
+                    lower
+                }
+            })
+        };
+        let row_part = letters(row, self.row_bits, self.row_letters);
+        let column_part = letters(column, self.column_bits, self.column_letters);
+        Some(row_part.chain(column_part).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_layout() {
+        let layout = SeatLayout::PUZZLE;
+        for (pass, row, column, id) in [
+            ("FBFBBFFRLR", 44, 5, 357),
+            ("BFFFBBFRRR", 70, 7, 567),
+            ("FFFBBBFRRR", 14, 7, 119),
+            ("BBFFBBFRLL", 102, 4, 820),
+        ] {
+            assert_eq!(layout.decode(pass), Ok(Seat { row, column, id }));
+            assert_eq!(layout.encode(row, column).unwrap(), pass);
+        }
+        assert_eq!(layout.encode(128, 0), None);
+        let e = layout.decode("FBFBBFFRLX").unwrap_err();
+        assert_eq!(
+            (e.column, e.message.as_str()),
+            (10, "unexpected character 'X'")
+        );
+        assert!(layout.decode("FBFBBFFRL").is_err());
+        assert!(layout.decode("FBFBBFFRLRL").is_err());
+    }
+
+    #[test]
+    fn test_other_layout() {
+        let layout = SeatLayout::from_spec("5,2,ud,<>").unwrap();
+        assert_eq!((layout.nr_rows(), layout.nr_columns()), (32, 4));
+        for id in 0..(32 * 4) {
+            let seat = layout.seat(id);
+            let pass = layout.encode(seat.row, seat.column).unwrap();
+            assert_eq!(pass.chars().count(), 7);
+            assert_eq!(layout.decode(&pass), Ok(seat));
+        }
+        assert_eq!(layout.encode(3, 1).unwrap(), "uuudd<>");
+        assert_eq!(layout.seat_id(3, 1), 13);
+
+        assert_eq!(SeatLayout::from_spec("7,3"), Ok(SeatLayout::PUZZLE));
+        assert!(SeatLayout::from_spec("0,3").is_err());
+        assert!(SeatLayout::from_spec("30,3").is_err());
+        assert!(SeatLayout::from_spec("7,3,FF,LR").is_err());
+        assert!(SeatLayout::from_spec("7,3,FB").is_err());
+    }
+}
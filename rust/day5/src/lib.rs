@@ -0,0 +1,72 @@
+//! Solutions to 2020: Advent of Code day 5
+//! By Peter Fornwall
+
+use common::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+pub mod generate;
+pub mod layout;
+pub mod seatmap;
+
+pub use layout::{Seat, SeatLayout};
+
+fn get_id(layout: &SeatLayout, x: &str) -> usize {
+    layout.decode(x).unwrap().id
+}
+
+/// Highest seat ID of the boarding passes.
+pub fn part1(v: &[&str]) -> usize {
+    part1_with(v, &SeatLayout::PUZZLE)
+}
+
+/// The missing seat ID, with the seats before and after it taken.
+pub fn part2(v: &[&str]) -> usize {
+    part2_with(v, &SeatLayout::PUZZLE)
+}
+
+/// Like `part1`, for the passes of another plane, as parsed by `parse_with`.
+pub fn part1_with(v: &[&str], layout: &SeatLayout) -> usize {
+    v.iter().map(|x| get_id(layout, x)).max().unwrap()
+}
+
+/// Like `part2`, for the passes of another plane, as parsed by `parse_with`.
+pub fn part2_with(v: &[&str], layout: &SeatLayout) -> usize {
+    let s: HashSet<usize> = v.iter().map(|x| get_id(layout, x)).collect();
+    let max_id = *s.iter().max().unwrap();
+    (1..max_id)
+        .find(|x| !s.contains(x) && s.contains(&(*x - 1)) && s.contains(&(*x + 1)))
+        .unwrap()
+}
+
+/// Parse one boarding pass per line, like `FBFBBFFRLR`.
+pub fn parse(content: &str) -> Result<Vec<&str>, ParseError> {
+    parse_with(content, &SeatLayout::PUZZLE)
+}
+
+/// Parse one boarding pass per line, with the letters of `layout`.
+pub fn parse_with<'a>(content: &'a str, layout: &SeatLayout) -> Result<Vec<&'a str>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|line| layout.check_pass(content, line.trim()))
+        .collect()
+}
+
+/// Day 5 as a `Solution`, as used by the aoc runner.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
@@ -1,48 +1,83 @@
 //! Solutions to 2020: Advent of Code day 5
 //! By Peter Fornwall
+//!
+//! Usage: day5 [INPUT] [--layout SPEC] [--map]
+//!        day5 --encode ROW,COLUMN [--layout SPEC]
+//!
+//! --layout reads the passes of another plane, given by its number of row
+//! and column bits and optionally its letters, like "6,2" or "6,2,FB,LR".
+//! --encode prints the boarding pass of a seat.
+//!
+//! --map draws which seats have passes, and lists the missing rows at the
+//! front and back, the empty seats between them and duplicate passes.
 
-use std::collections::HashSet;
-use std::fs;
+use day5::SeatLayout;
+use std::env;
+use std::process;
 
-fn bin_search(s: &[u8], upper_ch: char) -> usize {
-    s.iter()
-        .fold((0, 2usize.pow((s.len() - 1) as u32)), |acc, c| {
-            if *c == upper_ch as u8 {
-                return (acc.0 + acc.1, acc.1 / 2);
-            }
-            (acc.0, acc.1 / 2)
-        })
-        .0
-}
-
-fn get_id(x: &str) -> usize {
-    let row = bin_search(&x.as_bytes()[0..7], 'B');
-    let column = bin_search(&x.as_bytes()[7..10], 'R');
-    let id = row * 8 + column;
-    return id;
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: day5 [INPUT] [--layout SPEC] [--map]");
+    eprintln!("       day5 --encode ROW,COLUMN [--layout SPEC]");
+    process::exit(2);
 }
 
-fn part1(v: &Vec<&str>) -> usize {
-    let max_id = v.iter().map(|x| get_id(x)).max().unwrap();
-    println!("Part1: {}", max_id);
-    return max_id;
-}
-
-pub fn part2(v: &Vec<&str>) -> usize {
-    let s: HashSet<usize> = v.iter().map(|x| get_id(x)).collect();
-    let max_id = *s.iter().max().unwrap();
-    let my_id = (1..max_id)
-        .filter(|x| !s.contains(&x) && s.contains(&(*x - 1)) && s.contains(&(*x + 1)))
-        .next()
-        .unwrap();
-    println!("Part2: {}", my_id);
-    return my_id;
+fn parse_seat(s: &str) -> Option<(usize, usize)> {
+    let (row, column) = s.split_once(',')?;
+    Some((row.trim().parse().ok()?, column.trim().parse().ok()?))
 }
 
 fn main() {
-    let content = fs::read_to_string("../../inputs/day5_input.txt").expect("Cannot open file!");
-    let v: Vec<&str> = content.trim().split("\n").collect();
+    let mut input = None;
+    let mut layout = SeatLayout::PUZZLE;
+    let mut encode = None;
+    let mut map = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| exit_with_usage(&format!("Missing value for {}", arg)))
+        };
+        match arg.as_str() {
+            "--layout" => {
+                layout = SeatLayout::from_spec(&value()).unwrap_or_else(|e| exit_with_usage(&e))
+            }
+            "--encode" => {
+                let seat = value();
+                encode =
+                    Some(parse_seat(&seat).unwrap_or_else(|| {
+                        exit_with_usage(&format!("Expected ROW,COLUMN: {}", seat))
+                    }));
+            }
+            "--map" => map = true,
+            _ => input = Some(arg),
+        }
+    }
+    if let Some((row, column)) = encode {
+        match layout.encode(row, column) {
+            Some(pass) => println!("{}", pass),
+            None => exit_with_usage(&format!(
+                "No seat {},{} in a plane of {} rows of {} seats",
+                row,
+                column,
+                layout.nr_rows(),
+                layout.nr_columns()
+            )),
+        }
+        return;
+    }
+    let source = common::InputSource::from_arg(input.as_deref(), 5);
+    let content = common::read_or_exit(&source);
+    let v = common::unwrap_or_exit(day5::parse_with(&content, &layout), &source);
 
-    let _result_p1 = part1(&v);
-    let _result_p2 = part2(&v);
+    if map {
+        let map = day5::seatmap::SeatMap::new(&layout, &v);
+        print!("{}", map.render());
+        print!("{}", map.report());
+        return;
+    }
+    let result_p1 = day5::part1_with(&v, &layout);
+    println!("Part1: {}", result_p1);
+    let result_p2 = day5::part2_with(&v, &layout);
+    println!("Part2: {}", result_p2);
 }
@@ -0,0 +1,215 @@
+//! Occupancy of a plane from its boarding passes, drawn as a grid, with the
+//! empty seats, missing rows and duplicate passes that it shows.
+
+use crate::{Seat, SeatLayout};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::ops::{Range, RangeInclusive};
+
+/// Number of boarding passes of each seat of a plane.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeatMap {
+    layout: SeatLayout,
+    /// Number of passes by seat ID, for the seats with passes.
+    counts: BTreeMap<usize, usize>,
+}
+
+impl SeatMap {
+    /// Map of the passes of `v`, as parsed by `parse_with` with the layout.
+    pub fn new(layout: &SeatLayout, v: &[&str]) -> SeatMap {
+        let mut counts = BTreeMap::new();
+        for pass in v {
+            *counts.entry(layout.decode(pass).unwrap().id).or_insert(0) += 1;
+        }
+        SeatMap {
+            layout: *layout,
+            counts,
+        }
+    }
+
+    /// Number of passes of the seat.
+    pub fn count(&self, id: usize) -> usize {
+        self.counts.get(&id).copied().unwrap_or(0)
+    }
+
+    // Rows from the first to the last row with a taken seat.
+    fn taken_rows(&self) -> Option<RangeInclusive<usize>> {
+        let first = *self.counts.keys().next()?;
+        let last = *self.counts.keys().next_back()?;
+        Some(self.layout.seat(first).row..=self.layout.seat(last).row)
+    }
+
+    /// Rows at the front without any taken seat.
+    pub fn front_rows(&self) -> Range<usize> {
+        match self.taken_rows() {
+            Some(rows) => 0..*rows.start(),
+            None => 0..self.layout.nr_rows(),
+        }
+    }
+
+    /// Rows at the back without any taken seat, after the front rows if no
+    /// seat is taken.
+    pub fn back_rows(&self) -> Range<usize> {
+        let nr_rows = self.layout.nr_rows();
+        match self.taken_rows() {
+            Some(rows) => (rows.end() + 1)..nr_rows,
+            None => nr_rows..nr_rows,
+        }
+    }
+
+    /// Seats without a pass, except in the front and back rows.
+    pub fn empty_seats(&self) -> Vec<Seat> {
+        let rows = match self.taken_rows() {
+            Some(rows) => rows,
+            None => return Vec::new(),
+        };
+        let first = self.layout.seat_id(*rows.start(), 0);
+        let end = self.layout.seat_id(rows.end() + 1, 0);
+        (first..end)
+            .filter(|id| !self.counts.contains_key(id))
+            .map(|id| self.layout.seat(id))
+            .collect()
+    }
+
+    /// Runs of consecutive seat IDs of `empty_seats`.
+    pub fn empty_runs(&self) -> Vec<RangeInclusive<usize>> {
+        let mut runs: Vec<RangeInclusive<usize>> = Vec::new();
+        for seat in self.empty_seats() {
+            match runs.last_mut() {
+                Some(run) if *run.end() + 1 == seat.id => *run = *run.start()..=seat.id,
+                _ => runs.push(seat.id..=seat.id),
+            }
+        }
+        runs
+    }
+
+    /// Seats with more than one pass, and their number of passes.
+    pub fn duplicates(&self) -> Vec<(Seat, usize)> {
+        self.counts
+            .iter()
+            .filter(|(_, &count)| count > 1)
+            .map(|(&id, &count)| (self.layout.seat(id), count))
+            .collect()
+    }
+
+    /// The plane with one line per row, after its row number: `#` for a
+    /// seat with a pass, `!` for a seat with more than one and `.` for an
+    /// empty seat.
+    pub fn render(&self) -> String {
+        let width = (self.layout.nr_rows() - 1).to_string().len();
+        let mut s = String::new();
+        for row in 0..self.layout.nr_rows() {
+            s.push_str(&format!("{:>1$} ", row, width));
+            for column in 0..self.layout.nr_columns() {
+                s.push(match self.count(self.layout.seat_id(row, column)) {
+                    0 => '.',
+                    1 => '#',
+                    _ => '!',
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Missing rows, empty seats, runs of empty seats and duplicate passes.
+    pub fn report(&self) -> String {
+        let rows = |rows: Range<usize>| match rows.len() {
+            0 => String::from("none"),
+            1 => format!("{} (1 row)", rows.start),
+            n => format!("{}-{} ({} rows)", rows.start, rows.end - 1, n),
+        };
+        let mut s = String::new();
+        writeln!(s, "Missing front rows: {}", rows(self.front_rows())).unwrap();
+        writeln!(s, "Missing back rows: {}", rows(self.back_rows())).unwrap();
+        let empty = self.empty_seats();
+        writeln!(s, "Empty seats: {}", empty.len()).unwrap();
+        for seat in &empty {
+            writeln!(
+                s,
+                "  row {}, column {}, seat ID {}",
+                seat.row, seat.column, seat.id
+            )
+            .unwrap();
+        }
+        let runs = self.empty_runs();
+        writeln!(s, "Runs of empty seats: {}", runs.len()).unwrap();
+        for run in &runs {
+            match run.end() - run.start() + 1 {
+                1 => writeln!(s, "  {}", run.start()).unwrap(),
+                n => writeln!(s, "  {}-{} ({} seats)", run.start(), run.end(), n).unwrap(),
+            }
+        }
+        let duplicates = self.duplicates();
+        writeln!(s, "Duplicate passes: {}", duplicates.len()).unwrap();
+        for (seat, count) in &duplicates {
+            let pass = self.layout.encode(seat.row, seat.column).unwrap();
+            writeln!(
+                s,
+                "  {} (row {}, column {}, seat ID {}): {} passes",
+                pass, seat.row, seat.column, seat.id, count
+            )
+            .unwrap();
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_with;
+
+    #[test]
+    fn test_seat_map() {
+        // 8 rows of 4 seats, with rows 0 and 6-7 empty.
+        let layout = SeatLayout::from_spec("3,2").unwrap();
+        let passes: Vec<String> = [4, 5, 6, 7, 8, 11, 12, 17, 17, 18, 20, 21, 22, 23]
+            .iter()
+            .map(|&id| {
+                let seat = layout.seat(id);
+                layout.encode(seat.row, seat.column).unwrap()
+            })
+            .collect();
+        let content = passes.join("\n");
+        let v = parse_with(&content, &layout).unwrap();
+        let map = SeatMap::new(&layout, &v);
+
+        assert_eq!(map.count(17), 2);
+        assert_eq!(map.front_rows(), 0..1);
+        assert_eq!(map.back_rows(), 6..8);
+        let empty: Vec<usize> = map.empty_seats().iter().map(|x| x.id).collect();
+        assert_eq!(empty, [9, 10, 13, 14, 15, 16, 19]);
+        assert_eq!(map.empty_runs(), [9..=10, 13..=16, 19..=19]);
+        assert_eq!(map.duplicates(), [(layout.seat(17), 2)]);
+        assert_eq!(
+            map.render(),
+            "0 ....
+1 ####
+2 #..#
+3 #...
+4 .!#.
+5 ####
+6 ....
+7 ....
+"
+        );
+        let report = map.report();
+        assert!(
+            report.starts_with("Missing front rows: 0 (1 row)\nMissing back rows: 6-7 (2 rows)\n")
+        );
+        assert!(report.contains("  row 2, column 1, seat ID 9\n"));
+        assert!(report.contains("  13-16 (4 seats)\n"));
+        assert!(report
+            .ends_with("Duplicate passes: 1\n  BFFLR (row 4, column 1, seat ID 17): 2 passes\n"));
+    }
+
+    #[test]
+    fn test_empty_plane() {
+        let map = SeatMap::new(&SeatLayout::PUZZLE, &[]);
+        assert_eq!(map.front_rows(), 0..128);
+        assert_eq!(map.back_rows(), 128..128);
+        assert!(map.empty_seats().is_empty());
+        assert!(map.render().starts_with("  0 ........\n"));
+    }
+}
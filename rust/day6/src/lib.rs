@@ -0,0 +1,108 @@
+//! Solutions to 2020: Advent of Code day 6
+//! By Peter Fornwall
+
+use common::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+fn count_yes_answers(
+    groups: &[&str],
+    group_op: fn(&HashSet<char>, &HashSet<char>) -> HashSet<char>,
+    start_set: &HashSet<char>,
+) -> usize {
+    let mut result = 0;
+    for group in groups {
+        let answers: Vec<&str> = group.split('\n').collect();
+        let mut group_yes: HashSet<char> = start_set.clone();
+        for answer in answers {
+            let answer_set: HashSet<char> = answer.chars().collect();
+            group_yes = group_op(&group_yes, &answer_set);
+        }
+        result += group_yes.len();
+    }
+    result
+}
+
+/// Sum over the groups of questions anyone answered yes to.
+pub fn part1(groups: &[&str]) -> usize {
+    fn group_union(s: &HashSet<char>, s2: &HashSet<char>) -> HashSet<char> {
+        s.union(s2).cloned().collect()
+    }
+    count_yes_answers(groups, group_union, &HashSet::new())
+}
+
+/// Sum over the groups of questions everyone answered yes to.
+pub fn part2(groups: &[&str]) -> usize {
+    let alphabet_set: HashSet<char> = "abcdefghijklmnopqrstuvwxyz".chars().collect();
+    fn group_intersection(s: &HashSet<char>, s2: &HashSet<char>) -> HashSet<char> {
+        s.intersection(s2).cloned().collect()
+    }
+    count_yes_answers(groups, group_intersection, &alphabet_set)
+}
+
+/// Alternative iter-based solution for part1
+pub fn part1_iter(groups: &[&str]) -> usize {
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .chars()
+                .filter(|ch| ch.is_ascii_lowercase())
+                .collect::<HashSet<char>>()
+                .len()
+        })
+        .sum()
+}
+
+/// Alternative iter-based solution for part2
+pub fn part2_iter(groups: &[&str]) -> usize {
+    let alphabet_set: HashSet<char> = "abcdefghijklmnopqrstuvwxyz".chars().collect();
+
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .split('\n')
+                .map(|x| x.chars().collect())
+                .fold(alphabet_set.clone(), |acc, x| {
+                    acc.intersection(&x).cloned().collect()
+                })
+                .len()
+        })
+        .sum()
+}
+
+/// Split the answers into groups, separated by blank lines.
+pub fn parse(content: &str) -> Result<Vec<&str>, ParseError> {
+    let groups: Vec<&str> = content.trim().split("\n\n").collect();
+    for group in &groups {
+        for (i, ch) in group.char_indices() {
+            if !ch.is_ascii_lowercase() && ch != '\n' {
+                return Err(ParseError::at(
+                    content,
+                    &group[i..],
+                    format!("unexpected character '{}'", ch),
+                ));
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// Day 6 as a `Solution`, as used by the aoc runner.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
@@ -0,0 +1,176 @@
+//! Random bag rules. Bags are put on levels, and only contain bags on lower
+//! levels, so that the rules never loop and the shiny gold bag holds a
+//! limited number of bags.
+
+use common::{Generated, Rng};
+use std::collections::HashMap;
+
+const ADJECTIVES: [&str; 32] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy", "dashed", "mild",
+    "bold", "hazy", "rough", "smooth", "soft", "deep", "warm", "cool", "quiet", "loud", "worn",
+    "fresh",
+];
+const COLORS: [&str; 32] = [
+    "red",
+    "orange",
+    "yellow",
+    "green",
+    "blue",
+    "violet",
+    "white",
+    "black",
+    "gray",
+    "gold",
+    "silver",
+    "bronze",
+    "olive",
+    "plum",
+    "teal",
+    "tan",
+    "beige",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "purple",
+    "salmon",
+    "tomato",
+    "turquoise",
+    "aqua",
+    "chartreuse",
+];
+const LEVELS: usize = 7;
+const GOLD_LEVEL: usize = 3;
+
+struct Bag {
+    name: String,
+    level: usize,
+    contents: Vec<(i64, usize)>,
+}
+
+fn contains_gold(bags: &[Bag], bag: usize, memo: &mut HashMap<usize, bool>) -> bool {
+    if let Some(&result) = memo.get(&bag) {
+        return result;
+    }
+    let result = bags[bag]
+        .contents
+        .iter()
+        .any(|&(_, inside)| bags[inside].name == "shiny gold" || contains_gold(bags, inside, memo));
+    memo.insert(bag, result);
+    result
+}
+
+fn count_inside(bags: &[Bag], bag: usize) -> i64 {
+    bags[bag]
+        .contents
+        .iter()
+        .map(|&(nr, inside)| nr * (1 + count_inside(bags, inside)))
+        .sum()
+}
+
+/// Rules for `size` (2 to 1024) bag colors, one of them shiny gold.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let mut names: Vec<String> = Vec::new();
+    for adjective in ADJECTIVES.iter() {
+        for color in COLORS.iter() {
+            if (*adjective, *color) != ("shiny", "gold") {
+                names.push(format!("{} {}", adjective, color));
+            }
+        }
+    }
+    rng.shuffle(&mut names);
+    names.truncate(size.clamp(2, 1024) - 1);
+    names.push(String::from("shiny gold"));
+
+    let mut bags: Vec<Bag> = names
+        .into_iter()
+        .map(|name| {
+            let level = match name.as_str() {
+                "shiny gold" => GOLD_LEVEL,
+                _ => rng.below(LEVELS),
+            };
+            Bag {
+                name,
+                level,
+                contents: Vec::new(),
+            }
+        })
+        .collect();
+    for bag in 0..bags.len() {
+        let lower: Vec<usize> = (0..bags.len())
+            .filter(|&other| bags[other].level < bags[bag].level)
+            .collect();
+        if lower.is_empty() || rng.chance(10) {
+            continue;
+        }
+        let mut contents: Vec<(i64, usize)> = Vec::new();
+        // Make sure that some bags hold the shiny gold bag.
+        let gold = bags.len() - 1;
+        if bags[bag].level == GOLD_LEVEL + 1 && rng.chance(30) {
+            contents.push((rng.range(1..=5), gold));
+        }
+        for _ in 0..(1 + rng.below(4)) {
+            let inside = *rng.choose(&lower);
+            if contents.iter().all(|&(_, other)| other != inside) {
+                contents.push((rng.range(1..=5), inside));
+            }
+        }
+        bags[bag].contents = contents;
+    }
+
+    let mut memo = HashMap::new();
+    let part1 = (0..bags.len())
+        .filter(|&bag| contains_gold(&bags, bag, &mut memo))
+        .count();
+    let part2 = count_inside(&bags, bags.len() - 1);
+
+    let mut lines: Vec<String> = bags
+        .iter()
+        .map(|bag| {
+            let contents: Vec<String> = bag
+                .contents
+                .iter()
+                .map(|&(nr, inside)| {
+                    let plural = if nr == 1 { "" } else { "s" };
+                    format!("{} {} bag{}", nr, bags[inside].name, plural)
+                })
+                .collect();
+            if contents.is_empty() {
+                format!("{} bags contain no other bags.", bag.name)
+            } else {
+                format!("{} bags contain {}.", bag.name, contents.join(", "))
+            }
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    Generated {
+        input: lines.join("\n"),
+        part1: part1.into(),
+        part2: part2.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day7;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let generated = generate(seed, 300);
+            let input = Day7::parse(&generated.input).unwrap();
+            assert_eq!(input.len(), 300);
+            assert_eq!(Day7::part1(&input), generated.part1);
+            assert_eq!(Day7::part2(&input), generated.part2);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Solutions to 2020: Advent of Code day 7
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::collections::HashMap;
+
+pub mod generate;
+
+/// Map from bag color to the number and color of bags directly inside it.
+pub type Rules<'a> = HashMap<&'a str, Vec<(i32, &'a str)>>;
+
+fn search_gold(m: &Rules, s: &str) -> bool {
+    if s == "shiny gold" {
+        return true;
+    }
+    if let Some(inside) = m.get(s) {
+        for (_, x) in inside {
+            if search_gold(m, x) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Number of bag colors that can eventually contain a shiny gold bag.
+pub fn part1(m: &Rules) -> usize {
+    m.iter()
+        .filter(|(_, inside)| inside.iter().any(|(_, bag)| search_gold(m, bag)))
+        .count()
+}
+
+fn count_bags(m: &Rules, bag: &str) -> i32 {
+    m[bag].iter().fold(0, |acc, (nr, bag_inside)| {
+        acc + nr * count_bags(m, bag_inside)
+    }) + 1
+}
+
+/// Number of bags inside a shiny gold bag.
+pub fn part2(m: &Rules) -> i32 {
+    count_bags(m, "shiny gold") - 1
+}
+
+/// Parse rules like `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+pub fn parse(content: &str) -> Result<Rules<'_>, ParseError> {
+    let mut m: Rules = HashMap::new();
+    for i in content.trim().split('\n') {
+        let mut it = i.split("bags contain");
+        let bag_container = it.next().unwrap_or(i).trim();
+        let rest = it
+            .next()
+            .ok_or_else(|| ParseError::after(content, i, "expected 'bags contain'"))?
+            .trim();
+        let mut items: Vec<(i32, &str)> = Vec::new();
+        for x in rest.split(',') {
+            let x = x.trim();
+            if x.starts_with("no other") {
+                continue;
+            }
+            let space_index = x
+                .find(' ')
+                .ok_or_else(|| ParseError::after(content, x, "expected a count and a bag"))?;
+            let nr: i32 = parse_nr(content, &x[0..space_index])?;
+            let bag = x[(space_index + 1)..].split(" bag").next().unwrap_or("");
+            if bag.is_empty() {
+                return Err(ParseError::after(content, x, "missing bag color"));
+            }
+            items.push((nr, bag));
+        }
+        m.insert(bag_container, items);
+    }
+    // Every bag inside another bag must have its own rule.
+    for items in m.values() {
+        for (_, bag) in items {
+            if !m.contains_key(bag) {
+                return Err(ParseError::at(
+                    content,
+                    bag,
+                    format!("no rule for '{}' bags", bag),
+                ));
+            }
+        }
+    }
+    Ok(m)
+}
+
+/// Day 7 as a `Solution`, as used by the aoc runner.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Rules<'a>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+        dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+        bright white bags contain 1 shiny gold bag.
+        muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+        shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+        dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+        vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+        faded blue bags contain no other bags.
+        dotted black bags contain no other bags.
+        ";
+        let m = parse(input).unwrap();
+        let result = part1(&m);
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "shiny gold bags contain 2 dark red bags.
+        dark red bags contain 2 dark orange bags.
+        dark orange bags contain 2 dark yellow bags.
+        dark yellow bags contain 2 dark green bags.
+        dark green bags contain 2 dark blue bags.
+        dark blue bags contain 2 dark violet bags.
+        dark violet bags contain no other bags.
+        ";
+        let m = parse(input).unwrap();
+        let result = part2(&m);
+        assert_eq!(result, 126);
+    }
+}
@@ -0,0 +1,102 @@
+//! Random handheld programs with exactly one corrupted jmp.
+//!
+//! The program runs along a path of acc, nop and forward jmp instructions,
+//! where the forward jumps skip dead code. The corrupted jmp on the path jumps
+//! back, so the program loops. Any other change on the path before it also
+//! loops: a nop only has a zero or negative argument, and the first dead
+//! instruction after a forward jmp jumps back to the path.
+
+use common::{Generated, Rng};
+
+// Jump argument from `from` to a random earlier path position, or to itself,
+// which is the only choice at the start of the program.
+fn jump_back(rng: &mut Rng, path: &[usize], from: usize) -> i64 {
+    let to = if path.is_empty() || rng.chance(20) {
+        from
+    } else {
+        *rng.choose(path)
+    };
+    to as i64 - from as i64
+}
+
+/// Program with at least `size` (at least 2) instructions.
+pub fn generate(seed: u64, size: usize) -> Generated {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2);
+    let corrupt_at = size / 4 + rng.below(size / 2 + 1);
+    let mut program: Vec<(&str, i64)> = Vec::new();
+    let mut path: Vec<usize> = Vec::new();
+    let mut corrupted = false;
+    let mut acc_before = 0;
+    let mut acc_total = 0;
+    // A block of dead code can end past `corrupt_at`, or even past `size`.
+    while program.len() < size || !corrupted {
+        let pos = program.len();
+        if !corrupted && pos >= corrupt_at {
+            let arg = jump_back(&mut rng, &path, pos);
+            program.push(("jmp", arg));
+            path.push(pos);
+            acc_before = acc_total;
+            corrupted = true;
+            continue;
+        }
+        path.push(pos);
+        match rng.below(100) {
+            0..=59 => {
+                let arg = rng.range(-50..=50);
+                acc_total += arg;
+                program.push(("acc", arg));
+            }
+            60..=74 => {
+                let arg = -rng.range(0..=(pos as i64).min(20));
+                program.push(("nop", arg));
+            }
+            _ => {
+                let dead = 1 + rng.below(4);
+                program.push(("jmp", dead as i64 + 1));
+                program.push(("jmp", jump_back(&mut rng, &path, pos + 1)));
+                for _ in 1..dead {
+                    let op = *rng.choose(&["acc", "jmp", "nop"]);
+                    program.push((op, rng.range(-5..=5)));
+                }
+            }
+        }
+    }
+
+    let lines: Vec<String> = program
+        .iter()
+        .map(|(op, arg)| format!("{} {:+}", op, arg))
+        .collect();
+    Generated {
+        input: lines.join("\n"),
+        part1: acc_before.into(),
+        part2: acc_total.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day8;
+    use common::Solution;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let generated = generate(seed, 300);
+            let input = Day8::parse(&generated.input).unwrap();
+            assert!(input.len() >= 300);
+            assert_eq!(Day8::part1(&input), generated.part1);
+            assert_eq!(Day8::part2(&input), generated.part2);
+        }
+        for size in 0..10 {
+            for seed in 0..20 {
+                let generated = generate(seed, size);
+                let input = Day8::parse(&generated.input).unwrap();
+                assert!(input.len() >= size);
+                assert_eq!(Day8::part1(&input), generated.part1);
+                assert_eq!(Day8::part2(&input), generated.part2);
+            }
+        }
+    }
+}
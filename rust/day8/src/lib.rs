@@ -0,0 +1,158 @@
+//! Solutions to 2020: Advent of Code day 8
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+pub mod generate;
+
+/// Run the program until it terminates, or until an instruction would run a
+/// second time. The instruction at `swap_index`, if any, is run as nop if it is
+/// jmp and as jmp if it is nop. Returns whether the program terminated, and
+/// the value of the accumulator.
+pub fn run(v: &[(&str, i32)], swap_index: Option<usize>) -> (bool, i32) {
+    let mut seen: HashSet<usize> = HashSet::new();
+    let mut acc = 0;
+    let mut ip = 0usize;
+    let mut finished = false;
+    loop {
+        if seen.contains(&ip) {
+            break;
+        }
+        seen.insert(ip);
+        let mut inst = v[ip];
+        if swap_index == Some(ip) {
+            if inst.0 == "jmp" {
+                inst.0 = "nop"
+            } else if inst.0 == "nop" {
+                inst.0 = "jmp";
+            }
+        }
+        if inst.0 == "acc" || inst.0 == "nop" {
+            if inst.0 == "acc" {
+                acc += inst.1;
+            }
+            ip += 1;
+        } else if inst.0 == "jmp" {
+            ip = (ip as i32 + inst.1) as usize;
+        }
+        if ip >= v.len() {
+            if ip == v.len() {
+                finished = true;
+            }
+            break;
+        }
+    }
+    (finished, acc)
+}
+
+/// Value of the accumulator just before an instruction would run twice.
+pub fn part1(v: &[(&str, i32)]) -> i32 {
+    let (finished, acc) = run(v, None);
+    assert!(!finished);
+    acc
+}
+
+/// Value of the accumulator after the program terminates, when swapping
+/// the one jmp or nop that makes it terminate.
+pub fn part2(v: &[(&str, i32)]) -> i32 {
+    let mut result = 0;
+    for (swap_index, _) in v.iter().enumerate() {
+        if v[swap_index].0 == "jmp" || v[swap_index].0 == "nop" {
+            let (finished, acc) = run(v, Some(swap_index));
+            if finished {
+                result = acc;
+                break;
+            }
+        }
+    }
+    result
+}
+
+/// Parse one instruction per line, like `acc +1`.
+pub fn parse(content: &str) -> Result<Vec<(&str, i32)>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|x| {
+            let x = x.trim();
+            let mut it = x.split(' ');
+            let op = it.next().unwrap_or(x);
+            if !["acc", "jmp", "nop"].contains(&op) {
+                return Err(ParseError::at(
+                    content,
+                    op,
+                    format!("unknown operation '{}'", op),
+                ));
+            }
+            let arg = it
+                .next()
+                .ok_or_else(|| ParseError::after(content, op, "missing argument"))?;
+            Ok((op, parse_nr(content, arg)?))
+        })
+        .collect()
+}
+
+/// Day 8 as a `Solution`, as used by the aoc runner.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = Vec<(&'a str, i32)>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "nop +0
+        acc +1
+        jmp +4
+        acc +3
+        jmp -3
+        acc -99
+        acc +1
+        jmp -4
+        acc +6";
+        let v = parse(input).unwrap();
+        let result = part1(&v);
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "nop +0
+        acc +1
+        jmp +4
+        acc +3
+        jmp -3
+        acc -99
+        acc +1
+        jmp -4
+        acc +6";
+        let v = parse(input).unwrap();
+        let result = part2(&v);
+        assert_eq!(result, 8);
+        assert_eq!(run(&v, None), (false, 5));
+        assert_eq!(run(&v, Some(7)), (true, 8));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = parse("nop +0\nmul +2").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 1: unknown operation 'mul'");
+    }
+}
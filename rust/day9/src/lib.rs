@@ -0,0 +1,134 @@
+//! Solutions to 2020: Advent of Code day 9
+//! By Peter Fornwall
+
+use common::{parse_nr, Answer, ParseError, Solution};
+
+/// First number that is not the sum of two of the `preamble` numbers before it.
+pub fn part1(v: &[i64], preamble: usize) -> i64 {
+    let mut result = 0;
+
+    for i in preamble..v.len() {
+        let mut found = false;
+        'outer: for x1 in (i - preamble)..i {
+            for x2 in (x1 + 1)..i {
+                let sum = v[x1] + v[x2];
+                if sum == v[i] {
+                    found = true;
+                    break 'outer;
+                }
+            }
+        }
+        if !found {
+            result = v[i];
+        }
+    }
+    result
+}
+
+/// Sum of the smallest and largest number in a contiguous range of at least
+/// two numbers, that sums to `search_sum`.
+pub fn part2(v: &[i64], preamble: usize, search_sum: i64) -> i64 {
+    let mut result: i64 = 0;
+
+    'outer: for i in preamble..v.len() {
+        for x1 in (i - preamble)..i {
+            for x2 in (x1 + 1)..i {
+                let sum: i64 = v[x1..x2].iter().sum();
+                if sum == search_sum {
+                    result = v[x1..x2].iter().min().unwrap() + v[x1..x2].iter().max().unwrap();
+                    break 'outer;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Parse one number per line.
+pub fn parse(content: &str) -> Result<Vec<i64>, ParseError> {
+    content
+        .trim()
+        .split('\n')
+        .map(|x| parse_nr(content, x.trim()))
+        .collect()
+}
+
+/// Day 9 as a `Solution`, as used by the aoc runner.
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input<'a> = Vec<i64>;
+
+    fn parse(content: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(content)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part1(input, 25).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part2(input, 25, part1(input, 25)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input = "35
+        20
+        15
+        25
+        47
+        40
+        62
+        55
+        65
+        95
+        102
+        117
+        150
+        182
+        127
+        219
+        299
+        277
+        309
+        576";
+        let v = parse(input).unwrap();
+        let result = part1(&v, 5);
+        assert_eq!(result, 127);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "35
+        20
+        15
+        25
+        47
+        40
+        62
+        55
+        65
+        95
+        102
+        117
+        150
+        182
+        127
+        219
+        299
+        277
+        309
+        576";
+        let v = parse(input).unwrap();
+        let result_p1 = part1(&v, 5);
+        let result = part2(&v, 5, result_p1);
+        assert_eq!(result, 62);
+    }
+}